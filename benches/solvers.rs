@@ -0,0 +1,33 @@
+// Benchmarks every part that is not slow on the puzzle inputs, through the library instead of the
+// command line. Days can be picked like with `--days`, as in `cargo bench -- 1,3,10-16`.
+use std::time::Duration;
+
+use aoc2023::bench::Settings;
+use aoc2023::input::{self, Loader, Source};
+use aoc2023::output::Format;
+use aoc2023::registry::Days;
+use aoc2023::run::{self, Options};
+
+fn main() {
+    // Cargo passes `--bench` to benches without a harness
+    let days = match std::env::args().skip(1).find(|arg| !arg.starts_with('-')) {
+        Some(days) => days.parse::<Days>().unwrap_or_else(|e| panic!("{e}")),
+        None => Days::ALL,
+    };
+
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let loader = Loader::new(root.join(input::DEFAULT_DIR), Source::Default);
+    let settings = Settings::with_time(Duration::from_millis(500));
+
+    let options = Options {
+        days,
+        part: None,
+        include_slow: false,
+        limit: Some(Duration::from_secs(30)),
+        jobs: 1,
+        format: Format::Text,
+        stream: false,
+    };
+
+    run::bench(&loader, &options, &settings, None, None);
+}
@@ -0,0 +1,120 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+// The answer to a puzzle part. Numbers compare equal by value, regardless of whether they were
+// produced as a signed or an unsigned integer.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    Text(String),
+}
+
+impl Answer {
+    // The answer as an unsigned number if it is a non-negative integer.
+    fn as_unsigned(&self) -> Option<u128> {
+        match self {
+            Answer::Signed(x) => u128::try_from(*x).ok(),
+            Answer::Unsigned(x) => Some(*x),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        use Answer::*;
+
+        match (self, other) {
+            (Text(a), Text(b)) => a == b,
+            (Text(_), _) | (_, Text(_)) => false,
+            (Signed(a), Signed(b)) => a == b,
+            _ => self.as_unsigned().is_some() && self.as_unsigned() == other.as_unsigned(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(x) => x.fmt(f),
+            Answer::Unsigned(x) => x.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+// Anything that looks like an integer becomes a number, everything else is kept as text.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Answer, Infallible> {
+        if let Ok(x) = s.parse::<u128>() {
+            return Ok(Answer::Unsigned(x));
+        }
+
+        if let Ok(x) = s.parse::<i128>() {
+            return Ok(Answer::Signed(x));
+        }
+
+        Ok(Answer::Text(s.to_string()))
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Answer {
+                    Answer::$variant(x as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+impl_from!(Signed, i128, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn numbers_compare_by_value() {
+        assert_eq!(Answer::from(975i64), Answer::from(975u32));
+        assert_eq!(Answer::from(0usize), Answer::from(0i8));
+        assert_ne!(Answer::from(-1i64), Answer::from(u128::MAX));
+        assert_ne!(Answer::from(12u64), Answer::from("12"));
+    }
+
+    #[test]
+    fn parse_round_trips() {
+        for answer in [
+            Answer::from(13830919117339u64),
+            Answer::from(-42i64),
+            Answer::from("EAHRZUF"),
+        ] {
+            let parsed = answer.to_string().parse::<Answer>().unwrap();
+            assert_eq!(parsed, answer);
+        }
+
+        assert!(matches!("17".parse(), Ok(Answer::Unsigned(17))));
+        assert!(matches!("-17".parse(), Ok(Answer::Signed(-17))));
+    }
+}
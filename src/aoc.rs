@@ -0,0 +1,491 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::http::{self, Http, HttpError, Method, Request, Response};
+use crate::paths;
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+const YEAR: u16 = 2023;
+
+// Identifies these requests to the site, as its automation guidelines ask for.
+const USER_AGENT: &str = "github.com/toteload/aoc2023";
+
+// Least time between two requests to the site, also between separate runs.
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum SiteError {
+    NoSession,
+    Http(HttpError),
+    // The session token was not accepted
+    LoggedOut,
+    NotUnlocked { day: u8 },
+    Status { status: u16, body: String },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for SiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SiteError::NoSession => write!(
+                f,
+                "no session token, set AOC_SESSION or put it in {}",
+                config_path().map_or("the config file".to_string(), |p| p.display().to_string())
+            ),
+            SiteError::Http(e) => write!(f, "{e}"),
+            SiteError::LoggedOut => write!(f, "the session token is not valid anymore"),
+            SiteError::NotUnlocked { day } => write!(f, "day {day} is not unlocked yet"),
+            SiteError::Status { status, body } => {
+                let first = body.lines().next().unwrap_or("");
+                write!(f, "the site responded with status {status}: {first}")
+            }
+            SiteError::Io { path, source } => {
+                write!(f, "could not access {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SiteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SiteError::Http(e) => Some(e),
+            SiteError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<HttpError> for SiteError {
+    fn from(e: HttpError) -> SiteError {
+        SiteError::Http(e)
+    }
+}
+
+// `$XDG_CONFIG_HOME/aoc2023/session`, or `~/.config/aoc2023/session` if that is not set.
+fn config_path() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config.join("aoc2023").join("session"))
+}
+
+// Where the state that is kept between runs goes, `<target dir>/aoc-site`.
+pub fn state_dir() -> PathBuf {
+    paths::target_dir().join("aoc-site")
+}
+
+// The session cookie of the logged in account, from `AOC_SESSION` or else the config file.
+pub fn session() -> Result<String, SiteError> {
+    if let Some(session) = std::env::var("AOC_SESSION").ok().filter(|s| !s.is_empty()) {
+        return Ok(session);
+    }
+
+    config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or(SiteError::NoSession)
+}
+
+// The Advent of Code website, for the 2023 puzzles.
+pub struct Site {
+    http: Box<dyn Http>,
+    url: String,
+    session: String,
+    // Holds the time of the last request, so the rate limit also holds between runs
+    state: PathBuf,
+    interval: Duration,
+}
+
+impl Site {
+    // Uses `AOC_URL` instead of the real site if it is set.
+    pub fn from_env() -> Result<Site, SiteError> {
+        let url = std::env::var("AOC_URL").unwrap_or(DEFAULT_URL.to_string());
+
+        Ok(Site {
+            http: http::backend(&url),
+            url: url.trim_end_matches('/').to_string(),
+            session: session()?,
+            state: state_dir().join("last-request"),
+            interval: MIN_INTERVAL,
+        })
+    }
+
+    pub fn input(&self, day: u8) -> Result<String, SiteError> {
+        let response = self.send(Method::Get, &format!("/{YEAR}/day/{day}/input"), None)?;
+
+        match response.status {
+            200 => Ok(response.body),
+            404 => Err(SiteError::NotUnlocked { day }),
+            // Without a valid session the site asks to log in
+            400 | 500 if response.body.contains("log in") => Err(SiteError::LoggedOut),
+            status => Err(SiteError::Status {
+                status,
+                body: response.body,
+            }),
+        }
+    }
+
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Reply, SiteError> {
+        let form = format!("level={part}&answer={}", form_encode(answer));
+        let response = self.send(
+            Method::Post,
+            &format!("/{YEAR}/day/{day}/answer"),
+            Some(form),
+        )?;
+
+        match response.status {
+            200 => Ok(parse_reply(&response.body)),
+            404 => Err(SiteError::NotUnlocked { day }),
+            400 | 500 if response.body.contains("log in") => Err(SiteError::LoggedOut),
+            status => Err(SiteError::Status {
+                status,
+                body: response.body,
+            }),
+        }
+    }
+
+    fn send(
+        &self,
+        method: Method,
+        path: &str,
+        form: Option<String>,
+    ) -> Result<Response, SiteError> {
+        self.throttle()?;
+
+        let request = Request {
+            method,
+            url: format!("{}{path}", self.url),
+            headers: vec![
+                ("Cookie".to_string(), format!("session={}", self.session)),
+                ("User-Agent".to_string(), USER_AGENT.to_string()),
+            ],
+            form,
+        };
+
+        Ok(self.http.send(&request)?)
+    }
+
+    // Waits until enough time has passed since the last request and then records the new one.
+    fn throttle(&self) -> Result<(), SiteError> {
+        let io_error = |source| SiteError::Io {
+            path: self.state.clone(),
+            source,
+        };
+
+        let now = || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+        };
+
+        let last = std::fs::read_to_string(&self.state)
+            .ok()
+            .and_then(|s| s.trim().parse::<f64>().ok())
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok());
+
+        if let Some(wait) = last.and_then(|last| (last + self.interval).checked_sub(now())) {
+            eprintln!("Waiting {wait:.1?} before the next request");
+            thread::sleep(wait);
+        }
+
+        if let Some(dir) = self.state.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+
+        std::fs::write(&self.state, now().as_secs_f64().to_string()).map_err(io_error)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+// What the site said about a submitted answer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Reply {
+    Correct,
+    // Wrong answers have to wait a while before the next one
+    Wrong {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    // The previous answer was too recent, nothing was submitted
+    TooSoon {
+        wait: Duration,
+    },
+    // The part was already solved, or the part before it was not
+    WrongLevel,
+    // The text of a reply that was not recognized
+    Unknown(String),
+}
+
+fn form_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+// The text of the first `<article>` of the page, without the markup.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map_or(html, |(_, rest)| {
+            rest.split_once("</article>").map_or(rest, |(a, _)| a)
+        });
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Reads a wait like `1m 23s` or `45s`, which is how the site tells how long is left.
+fn parse_wait(s: &str) -> Option<Duration> {
+    let mut secs = 0;
+
+    for part in s.split_whitespace() {
+        let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let n = n.parse::<u64>().ok()?;
+
+        secs += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(secs))
+}
+
+// Reads a penalty like `one minute` or `5 minutes`, which follows a wrong answer.
+fn parse_penalty(s: &str) -> Option<Duration> {
+    let mut words = s.split_whitespace();
+
+    let n = match words.next()? {
+        "one" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+
+    match words.next()? {
+        "minute" | "minutes" => Some(Duration::from_secs(60 * n)),
+        "second" | "seconds" => Some(Duration::from_secs(n)),
+        _ => None,
+    }
+}
+
+pub fn parse_reply(html: &str) -> Reply {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        return Reply::Correct;
+    }
+
+    if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+
+        let wait = text
+            .split_once("wait ")
+            .and_then(|(_, rest)| parse_penalty(rest));
+
+        return Reply::Wrong { hint, wait };
+    }
+
+    if text.contains("You gave an answer too recently") {
+        let wait = text
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .and_then(|(wait, _)| parse_wait(wait));
+
+        return Reply::TooSoon {
+            wait: wait.unwrap_or(MIN_INTERVAL),
+        };
+    }
+
+    if text.contains("You don't seem to be solving the right level") {
+        return Reply::WrongLevel;
+    }
+
+    Reply::Unknown(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_reply, Hint, Reply, Site, SiteError};
+    use crate::http::PlainHttp;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    // Answers a single request with `response` and returns the request it got.
+    fn stub(response: String) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+
+            // Read up to the end of the headers and then the body, if there is one.
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+
+                request.extend_from_slice(&buf[..n]);
+
+                let text = String::from_utf8_lossy(&request);
+                let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                    continue;
+                };
+
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |n| n.parse().unwrap());
+
+                if body.len() >= length {
+                    break;
+                }
+            }
+
+            stream.write_all(response.as_bytes()).unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        (url, handle)
+    }
+
+    fn site(url: String) -> Site {
+        let state = std::env::temp_dir().join(format!(
+            "aoc2023-test-{}-{}",
+            std::process::id(),
+            url.rsplit(':').next().unwrap()
+        ));
+
+        Site {
+            http: Box::new(PlainHttp),
+            url,
+            session: "secret".to_string(),
+            state,
+            interval: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn input_is_fetched_with_the_session() {
+        let (url, server) = stub("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n1 2 3\n".to_string());
+        let input = site(url).input(5).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1 2 3\n");
+        assert!(request.starts_with("GET /2023/day/5/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=secret\r\n"));
+    }
+
+    #[test]
+    fn locked_days_are_reported() {
+        let (url, server) = stub("HTTP/1.1 404 Not Found\r\n\r\nnot yet".to_string());
+        let res = site(url).input(25);
+        server.join().unwrap();
+
+        assert!(matches!(res, Err(SiteError::NotUnlocked { day: 25 })));
+    }
+
+    #[test]
+    fn replies_are_recognized() {
+        let page =
+            |text: &str| format!("<html><main><article><p>{text}</p></article></main></html>");
+
+        assert_eq!(
+            parse_reply(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            )),
+            Reply::Correct
+        );
+        assert_eq!(
+            parse_reply(&page("That's not the right answer; your answer is too high. Please wait one minute before trying again.")),
+            Reply::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_reply(&page(
+                "That's not the right answer. please wait 5 minutes before trying again."
+            )),
+            Reply::Wrong {
+                hint: None,
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse_reply(&page(
+                "You gave an answer too recently. You have 1m 23s left to wait."
+            )),
+            Reply::TooSoon {
+                wait: Duration::from_secs(83)
+            }
+        );
+        assert_eq!(
+            parse_reply(&page("You don't seem to be solving the right level.")),
+            Reply::WrongLevel
+        );
+        assert_eq!(
+            parse_reply("<article>Something   else</article>"),
+            Reply::Unknown("Something else".to_string())
+        );
+    }
+
+    #[test]
+    fn answers_are_posted_as_a_form() {
+        let body = "<article><p>That's the right answer!</p></article>";
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let (url, server) = stub(response);
+
+        let reply = site(url).submit(7, 2, "a b&c").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(reply, Reply::Correct);
+        assert!(request.starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=a+b%26c"));
+    }
+}
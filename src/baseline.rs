@@ -0,0 +1,340 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::bench::{format_time, Benchmark};
+use crate::paths;
+use crate::stats::Summary;
+
+// Saved benchmark results that later runs can be compared against. They are kept in
+// `<target dir>/aoc-bench/<name>.txt`, which is a plain text file with one `key<TAB>value` pair
+// per line and a `bench` line per benchmark:
+//
+//     bench<TAB><day><TAB><part><TAB><mean><TAB><stddev><TAB><median><TAB><min><TAB><p95><TAB><ci low>
+//          <TAB><ci high><TAB><outliers><TAB><space separated samples>
+//
+// The part is `parse` for the parsing of a day that parses separately. All times are in seconds.
+pub struct Baseline {
+    pub name: String,
+    pub revision: String,
+    pub cpu: String,
+    pub benchmarks: Vec<Benchmark>,
+}
+
+#[derive(Debug)]
+pub enum BaselineError {
+    InvalidName(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for BaselineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BaselineError::InvalidName(name) => {
+                write!(f, "`{name}` can not be used as a baseline name")
+            }
+            BaselineError::Io { path, source } => {
+                write!(f, "could not access {}: {source}", path.display())
+            }
+            BaselineError::Parse {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{line}: {message}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for BaselineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BaselineError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn path(name: &str) -> Result<PathBuf, BaselineError> {
+    let is_valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !name.starts_with('.');
+
+    if !is_valid {
+        return Err(BaselineError::InvalidName(name.to_string()));
+    }
+
+    Ok(paths::target_dir()
+        .join("aoc-bench")
+        .join(format!("{name}.txt")))
+}
+
+// The commit that is checked out, with `-dirty` appended if there are uncommitted changes.
+fn git_revision() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+
+    let Some(revision) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+
+    match git(&["status", "--porcelain", "--untracked-files=no"]) {
+        Some(status) if !status.is_empty() => format!("{revision}-dirty"),
+        _ => revision,
+    }
+}
+
+fn cpu_name() -> String {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+
+    cpuinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("model name"))
+        .and_then(|rest| rest.split_once(':'))
+        .map_or(std::env::consts::ARCH.to_string(), |(_, name)| {
+            name.trim().to_string()
+        })
+}
+
+pub fn save(name: &str, benchmarks: &[Benchmark]) -> Result<PathBuf, BaselineError> {
+    let path = path(name)?;
+
+    let mut text = String::from("# aoc2023 benchmark baseline\n");
+    text += &format!("revision\t{}\n", git_revision());
+    text += &format!("cpu\t{}\n", cpu_name());
+
+    for b in benchmarks {
+        let s = &b.stats;
+        let samples = s
+            .samples
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let part = b.part.map_or("parse".to_string(), |p| p.to_string());
+        text += &format!(
+            "bench\t{}\t{part}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{samples}\n",
+            b.day, s.mean, s.stddev, s.median, s.min, s.p95, s.ci.0, s.ci.1, s.outliers
+        );
+    }
+
+    let io_error = |source| BaselineError::Io {
+        path: path.clone(),
+        source,
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(io_error)?;
+    }
+
+    std::fs::write(&path, text).map_err(io_error)?;
+
+    Ok(path)
+}
+
+pub fn load(name: &str) -> Result<Baseline, BaselineError> {
+    let path = path(name)?;
+
+    let text = std::fs::read_to_string(&path).map_err(|source| BaselineError::Io {
+        path: path.clone(),
+        source,
+    })?;
+
+    parse(name, &text).map_err(|(line, message)| BaselineError::Parse {
+        path,
+        line,
+        message,
+    })
+}
+
+// On failure returns the 1-based line number and what is wrong with it.
+fn parse(name: &str, text: &str) -> Result<Baseline, (usize, String)> {
+    let mut baseline = Baseline {
+        name: name.to_string(),
+        revision: "unknown".to_string(),
+        cpu: "unknown".to_string(),
+        benchmarks: Vec::new(),
+    };
+
+    for (i, line) in text.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let number = |s: &str| {
+            s.parse::<f64>()
+                .map_err(|_| (i + 1, format!("`{s}` is not a valid number")))
+        };
+
+        match fields.as_slice() {
+            ["revision", revision] => baseline.revision = revision.to_string(),
+            ["cpu", cpu] => baseline.cpu = cpu.to_string(),
+            ["bench", day, part, mean, stddev, median, min, p95, ci_low, ci_high, outliers, samples] =>
+            {
+                let day = day
+                    .parse::<u8>()
+                    .map_err(|_| (i + 1, format!("`{day}` is not a valid day")))?;
+                let part = match *part {
+                    "parse" => None,
+                    part => Some(
+                        part.parse::<u8>()
+                            .map_err(|_| (i + 1, format!("`{part}` is not a valid part")))?,
+                    ),
+                };
+
+                let stats = Summary {
+                    mean: number(mean)?,
+                    stddev: number(stddev)?,
+                    median: number(median)?,
+                    min: number(min)?,
+                    p95: number(p95)?,
+                    ci: (number(ci_low)?, number(ci_high)?),
+                    outliers: outliers
+                        .parse()
+                        .map_err(|_| (i + 1, format!("`{outliers}` is not a valid count")))?,
+                    samples: samples
+                        .split_ascii_whitespace()
+                        .map(number)
+                        .collect::<Result<_, _>>()?,
+                };
+
+                baseline.benchmarks.push(Benchmark::new(day, part, stats));
+            }
+            _ => {
+                return Err((
+                    i + 1,
+                    "unexpected line, the baseline may have been saved by an older version"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+
+    Ok(baseline)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Change {
+    Faster,
+    Slower,
+    // The difference is too small compared to the spread of the samples to mean anything
+    Noise,
+}
+
+// Changes smaller than this fraction of the old mean are treated as noise, even if they are
+// statistically significant. Timings on a desktop drift by about this much between runs.
+const NOISE_THRESHOLD: f64 = 0.02;
+
+// A change is only significant if the confidence intervals of both means do not overlap.
+fn change(old: &Summary, new: &Summary) -> Change {
+    if (new.mean - old.mean).abs() < NOISE_THRESHOLD * old.mean {
+        Change::Noise
+    } else if new.ci.1 < old.ci.0 {
+        Change::Faster
+    } else if new.ci.0 > old.ci.1 {
+        Change::Slower
+    } else {
+        Change::Noise
+    }
+}
+
+pub fn print_comparison(baseline: &Baseline, benchmarks: &[Benchmark]) {
+    println!(
+        "\nCompared to baseline `{}` (revision {}):",
+        baseline.name, baseline.revision
+    );
+
+    let cpu = cpu_name();
+    if baseline.cpu != cpu {
+        println!(
+            "note: the baseline was recorded on a different CPU ({})",
+            baseline.cpu
+        );
+    }
+
+    for new in benchmarks {
+        let name = new.name();
+        let Some(old) = baseline
+            .benchmarks
+            .iter()
+            .find(|b| (b.day, b.part) == (new.day, new.part))
+        else {
+            println!("{name} | not in baseline");
+            continue;
+        };
+
+        let (old, new) = (&old.stats, &new.stats);
+        let delta = (new.mean - old.mean) * 100.0 / old.mean;
+        let change = match change(old, new) {
+            Change::Faster => "faster",
+            Change::Slower => "slower",
+            Change::Noise => "noise",
+        };
+
+        println!(
+            "{name} | {} -> {} | {:>+6.1}% | {change}",
+            format_time(old.mean),
+            format_time(new.mean),
+            delta
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{change, parse, Change};
+    use crate::bench::Benchmark;
+    use crate::stats::Summary;
+    use std::time::Duration;
+
+    fn summary(micros: &[u64]) -> Summary {
+        let samples = micros.iter().map(|&x| Duration::from_micros(x)).collect();
+        Benchmark::from_samples(1, Some(1), samples).stats
+    }
+
+    #[test]
+    fn parse_round_trips_benchmarks() {
+        let text = "# aoc2023 benchmark baseline\nrevision\tabc123\ncpu\tSome CPU\nbench\t1\t1\t0.5\t0.1\t0.4\t0.4\t0.6\t0.45\t0.55\t0\t0.4 0.6\n";
+        let baseline = parse("main", text).unwrap();
+
+        assert_eq!(baseline.revision, "abc123");
+        assert_eq!(baseline.cpu, "Some CPU");
+        assert_eq!(baseline.benchmarks.len(), 1);
+        assert_eq!(baseline.benchmarks[0].name(), "Day  1, part 1");
+        assert_eq!(baseline.benchmarks[0].stats.ci, (0.45, 0.55));
+        assert_eq!(baseline.benchmarks[0].stats.samples, [0.4, 0.6]);
+
+        assert_eq!(
+            parse("main", "bench\t1\t1\t0.5").err().map(|e| e.0),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn change_separates_signal_from_noise() {
+        let old = summary(&[100, 101, 99, 100, 102, 98, 100, 101, 99, 100]);
+        let fast = summary(&[80, 81, 79, 80, 82, 78, 80, 81, 79, 80]);
+        let same = summary(&[101, 99, 100, 102, 98, 100, 99, 101, 100, 100]);
+
+        assert_eq!(change(&old, &fast), Change::Faster);
+        assert_eq!(change(&fast, &old), Change::Slower);
+        assert_eq!(change(&old, &same), Change::Noise);
+    }
+}
@@ -0,0 +1,250 @@
+use std::any::Any;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::SolveError;
+use crate::memory::{self, Usage};
+use crate::registry::Solver;
+use crate::runner;
+
+use crate::stats::{self, Summary};
+
+pub struct Settings {
+    // Time spent running the solver before any samples are taken
+    pub warm_up: Duration,
+    // Time that sampling should take, which decides the number of samples
+    pub measure: Duration,
+    pub min_samples: usize,
+    pub max_samples: usize,
+}
+
+impl Settings {
+    // Spends a tenth of `time` on warming up and the rest on measuring.
+    pub fn with_time(time: Duration) -> Settings {
+        Settings {
+            warm_up: time / 10,
+            measure: time - time / 10,
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+// Times `f`, which should use `black_box` on its input so the work is not optimized away.
+pub fn bench<T>(settings: &Settings, mut f: impl FnMut() -> T) -> Vec<Duration> {
+    // Warm up the caches and the branch predictor. This also tells us roughly how long a single
+    // run takes, so we know how many samples fit in the measuring time.
+    let start = Instant::now();
+    let mut runs = 0;
+
+    while runs == 0 || start.elapsed() < settings.warm_up {
+        black_box(f());
+        runs += 1;
+    }
+
+    let estimate = start.elapsed().as_secs_f64() / runs as f64;
+    let sample_count = ((settings.measure.as_secs_f64() / estimate) as usize)
+        .clamp(settings.min_samples, settings.max_samples);
+
+    let mut samples = Vec::with_capacity(sample_count);
+
+    for _ in 0..sample_count {
+        let start = Instant::now();
+        black_box(f());
+        let t = start.elapsed();
+        samples.push(t);
+    }
+
+    samples
+}
+
+// Runs `f` once under the time limit, to check that it finishes and gives an answer before it is
+// sampled. Prints why not and returns `None` otherwise.
+fn try_once<T: Send + 'static>(
+    name: &str,
+    limit: Option<Duration>,
+    f: impl FnOnce() -> Result<T, SolveError> + Send + 'static,
+) -> Option<T> {
+    match runner::with_limit(limit, move || runner::catch_panic(f)) {
+        Some(Ok(Ok(x))) => return Some(x),
+        Some(Ok(Err(e))) => eprintln!("Skipping {name}: {e}"),
+        Some(Err(message)) => eprintln!("Skipping {name}: panicked: {message}"),
+        None => eprintln!(
+            "Skipping {name}: TIMEOUT after {:?}",
+            limit.unwrap_or_default()
+        ),
+    }
+
+    None
+}
+
+// Benchmarks the given solvers of a single day. Days that parse separately get their own row for
+// parsing, because both parts share the parsed input. Their parts are then timed without parsing.
+pub fn bench_day(
+    day: u8,
+    text: &Arc<str>,
+    solvers: &[&'static Solver],
+    settings: &Settings,
+    limit: Option<Duration>,
+) -> Vec<Benchmark> {
+    let mut benchmarks = Vec::new();
+    let mut parsed = None;
+
+    if let Some(phases) = solvers.iter().find_map(|s| s.phases.as_ref()) {
+        let parse = phases.parse;
+        let t = Arc::clone(text);
+
+        let Some(p) = try_once(&format!("day {day}"), limit, move || parse(&t)) else {
+            return benchmarks;
+        };
+
+        let samples = bench(settings, || parse(black_box(text)));
+        let mut benchmark = Benchmark::from_samples(day, None, samples);
+        benchmark.memory = memory::measure(|| parse(text));
+        benchmarks.push(benchmark);
+
+        parsed = Some(Arc::<dyn Any + Send + Sync>::from(p));
+    }
+
+    for solver in solvers {
+        let phases = solver.phases.as_ref().zip(parsed.as_ref());
+
+        let finished = match phases {
+            Some((phases, parsed)) => {
+                let (solve, parsed) = (phases.solve, Arc::clone(parsed));
+                try_once(solver.name, limit, move || solve(&*parsed))
+            }
+            None => {
+                let (run, t) = (solver.run, Arc::clone(text));
+                try_once(solver.name, limit, move || run(&t))
+            }
+        };
+
+        if finished.is_none() {
+            continue;
+        }
+
+        let (samples, memory) = match phases {
+            Some((phases, parsed)) => (
+                bench(settings, || (phases.solve)(black_box(&**parsed))),
+                memory::measure(|| (phases.solve)(&**parsed)),
+            ),
+            None => (
+                bench(settings, || (solver.run)(black_box(text))),
+                memory::measure(|| (solver.run)(text)),
+            ),
+        };
+
+        let mut benchmark = Benchmark::from_samples(day, Some(solver.part), samples);
+        benchmark.memory = memory;
+        benchmarks.push(benchmark);
+    }
+
+    benchmarks
+}
+
+pub fn print_time(secs: f64) {
+    print!("{}", format_time(secs));
+}
+
+// Bytes in binary units, in the same width as `format_time`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [(&str, u64); 3] = [("GB", 1 << 30), ("MB", 1 << 20), ("KB", 1 << 10)];
+
+    match UNITS.iter().find(|&&(_, size)| bytes >= size) {
+        Some((unit, size)) => format!("{:>3.0}{unit}", bytes as f64 / *size as f64),
+        None => format!("{bytes:>4}B"),
+    }
+}
+
+pub fn format_time(secs: f64) -> String {
+    if secs >= 1.0 {
+        format!("{:>3.0}s", secs)
+    } else if secs > 0.001 {
+        format!("{:>3.0}ms", secs * 1_000.0)
+    } else if secs > 0.000_001 {
+        format!("{:>3.0}μs", secs * 1_000_000.0)
+    } else {
+        format!("{:>3.0}ns", secs * 1_000_000_000.0)
+    }
+}
+
+#[derive(Debug)]
+pub struct Benchmark {
+    pub day: u8,
+    // `None` for the parsing of a day that parses separately
+    pub part: Option<u8>,
+    pub stats: Summary,
+    pub percentage: f64,
+    // What a single run allocated, if allocations are counted
+    pub memory: Option<Usage>,
+}
+
+impl Benchmark {
+    pub fn new(day: u8, part: Option<u8>, stats: Summary) -> Benchmark {
+        Benchmark {
+            day,
+            part,
+            stats,
+            percentage: 0.0,
+            memory: None,
+        }
+    }
+
+    pub fn from_samples(day: u8, part: Option<u8>, samples: Vec<Duration>) -> Benchmark {
+        let samples = samples.iter().map(|d| d.as_secs_f64()).collect();
+        Benchmark::new(day, part, stats::summarize(samples))
+    }
+
+    pub fn name(&self) -> String {
+        match self.part {
+            Some(part) => format!("Day {:2}, part {part}", self.day),
+            None => format!("Day {:2}, parse", self.day),
+        }
+    }
+
+    pub fn print_header() {
+        print!(
+            "{:14} | {:^13} | {:^13} | {:>5} | {:>5} | {:>5} | {:>7} | share",
+            "", "mean +- std", "95% CI", "med", "min", "p95", "samples"
+        );
+
+        if memory::ENABLED {
+            print!(" | {:>8} | {:>5} | {:>5}", "allocs", "bytes", "peak");
+        }
+
+        println!();
+    }
+
+    pub fn print(&self) {
+        let s = &self.stats;
+
+        print!("{:14} | ", self.name());
+        print_time(s.mean);
+        print!(" +- ");
+        print_time(s.stddev);
+        print!(" | ");
+        print_time(s.ci.0);
+        print!(" - ");
+        print_time(s.ci.1);
+        print!(" | ");
+        print_time(s.median);
+        print!(" | ");
+        print_time(s.min);
+        print!(" | ");
+        print_time(s.p95);
+        print!(" | {:>7} | {:>4.1}%", s.samples.len(), self.percentage);
+
+        if let Some(m) = &self.memory {
+            print!(
+                " | {:>8} | {} | {}",
+                m.allocations,
+                format_bytes(m.bytes),
+                format_bytes(m.peak)
+            );
+        }
+
+        println!();
+    }
+}
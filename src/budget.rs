@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use crate::bench::{format_time, Benchmark};
+
+const DAYS: u8 = 25;
+
+// Compares the benchmark results against a time budget for all days together. The budget is split
+// evenly over the days, and the share of a day evenly over its two parts. Parsing a day that parses
+// separately serves both parts, so it only counts towards the share of the day. The headroom is
+// what is left after the shares of the days that were not benchmarked, and is split over the
+// `unsolved` parts of the days that were.
+pub fn print_budget(benchmarks: &[Benchmark], budget: Duration, unsolved: usize) {
+    let budget = budget.as_secs_f64();
+    let day_share = budget / DAYS as f64;
+    let part_share = day_share / 2.0;
+
+    println!(
+        "\nBudget: {} in total, {} per day, {} per part",
+        time(budget),
+        time(day_share),
+        time(part_share)
+    );
+
+    let mut total_time = 0.0;
+    let mut benchmarked_days = 0;
+
+    for day in 1..=DAYS {
+        let rows = benchmarks
+            .iter()
+            .filter(|b| b.day == day)
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            continue;
+        }
+
+        let day_time = rows.iter().map(|b| b.stats.mean).sum::<f64>();
+        total_time += day_time;
+        benchmarked_days += 1;
+
+        print!(
+            "Day {day:2} | {} of {} | {:>6.1}%",
+            format_time(day_time),
+            format_time(day_share),
+            day_time * 100.0 / day_share
+        );
+
+        let over = rows
+            .iter()
+            .filter_map(|b| Some((b.part?, b.stats.mean)))
+            .filter(|&(_, mean)| mean > part_share)
+            .map(|(part, mean)| format!("part {part} takes {}", time(mean)))
+            .collect::<Vec<_>>();
+
+        match (day_time > day_share, over.is_empty()) {
+            (false, true) => println!(),
+            (true, true) => println!(" | OVER"),
+            (_, false) => println!(" | OVER, {}", over.join(", ")),
+        }
+    }
+
+    // The days that were not benchmarked still need their share, so it is not headroom.
+    let reserved = (DAYS - benchmarked_days) as f64 * day_share;
+    let remaining = budget - total_time - reserved;
+
+    println!(
+        "\nTotal: {} of {} ({:.1}%)",
+        time(total_time),
+        time(budget),
+        total_time * 100.0 / budget
+    );
+
+    if benchmarked_days < DAYS {
+        println!(
+            "Reserved: {} for the {} days that were not benchmarked",
+            time(reserved),
+            DAYS - benchmarked_days
+        );
+    }
+
+    if remaining < 0.0 {
+        println!("Over budget by {}", time(-remaining));
+    } else if unsolved > 0 {
+        println!(
+            "Headroom: {} left for the {unsolved} unsolved parts, {} each",
+            time(remaining),
+            time(remaining / unsolved as f64)
+        );
+    } else {
+        println!("Headroom: {} left", time(remaining));
+    }
+}
+
+fn time(secs: f64) -> String {
+    format_time(secs).trim().to_string()
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::answer::Answer;
+use crate::paths;
+use crate::registry::Solver;
+use crate::util::{escape_field, fnv1a, unescape_field};
+
+// Code that every day can use, so changing it could change any answer.
+const SHARED: [&str; 4] = [
+    include_str!("answer.rs"),
+    include_str!("error.rs"),
+    include_str!("graph.rs"),
+    include_str!("util.rs"),
+];
+
+// Day, part and the hash of the input
+type Key = (u8, u8, u64);
+// Version and answer
+type Entry = (u64, Answer);
+
+// The answers of earlier runs, so they do not have to be computed again. They are kept in
+// `<target dir>/aoc-cache/answers.txt`, which has a line per day, part and input:
+//
+//     <day><TAB><part><TAB><input hash><TAB><version><TAB><answer>
+//
+// Backslashes, tabs and line breaks in the answer are escaped with a backslash.
+// The version is a hash of the code of the day. An answer is only used when both the input and the
+// version match, but it is kept after the code changes, to tell if the answer changed with it.
+#[derive(Default)]
+pub struct Cache {
+    path: PathBuf,
+    entries: HashMap<Key, Entry>,
+    changed: bool,
+}
+
+#[derive(Debug)]
+pub enum CacheError {
+    Io { path: PathBuf, source: io::Error },
+    Parse { path: PathBuf, line: usize },
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheError::Io { path, source } => {
+                write!(f, "could not access {}: {source}", path.display())
+            }
+            CacheError::Parse { path, line } => {
+                write!(f, "{}:{line}: not a cached answer", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for CacheError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CacheError::Io { source, .. } => Some(source),
+            CacheError::Parse { .. } => None,
+        }
+    }
+}
+
+pub fn default_path() -> PathBuf {
+    paths::target_dir().join("aoc-cache").join("answers.txt")
+}
+
+pub fn input_hash(text: &str) -> u64 {
+    fnv1a([text.as_bytes()])
+}
+
+// Changes whenever the code of the day or the code it shares with the other days changes.
+pub fn version(solver: &Solver) -> u64 {
+    let name = solver.name.as_bytes();
+    let shared = SHARED.iter().map(|s| s.as_bytes());
+    fnv1a([name, solver.source.as_bytes()].into_iter().chain(shared))
+}
+
+fn parse_line(line: &str) -> Option<(Key, Entry)> {
+    let fields = line.splitn(5, '\t').collect::<Vec<_>>();
+    let &[day, part, input, version, answer] = fields.as_slice() else {
+        return None;
+    };
+
+    let key = (
+        day.parse().ok()?,
+        part.parse().ok()?,
+        u64::from_str_radix(input, 16).ok()?,
+    );
+    let Ok(answer) = unescape_field(answer)?.parse();
+
+    Some((key, (u64::from_str_radix(version, 16).ok()?, answer)))
+}
+
+fn format_line((day, part, input): Key, (version, answer): &Entry) -> String {
+    let answer = escape_field(&answer.to_string());
+    format!("{day}\t{part}\t{input:016x}\t{version:016x}\t{answer}\n")
+}
+
+impl Cache {
+    // An empty cache, which is saved to `path`.
+    pub fn new(path: &Path) -> Cache {
+        Cache {
+            path: path.to_path_buf(),
+            ..Cache::default()
+        }
+    }
+
+    // A cache that does not exist yet is empty.
+    pub fn load(path: &Path) -> Result<Cache, CacheError> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => {
+                return Err(CacheError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+
+        let entries = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                parse_line(line).ok_or(CacheError::Parse {
+                    path: path.to_path_buf(),
+                    line: i + 1,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Cache {
+            path: path.to_path_buf(),
+            entries,
+            changed: false,
+        })
+    }
+
+    // The answer of this version of `solver` for the input, if it was computed before.
+    pub fn get(&self, solver: &Solver, input: u64) -> Option<&Answer> {
+        self.entries
+            .get(&(solver.day, solver.part, input))
+            .filter(|(cached, _)| *cached == version(solver))
+            .map(|(_, answer)| answer)
+    }
+
+    // Stores a freshly computed answer. Returns the answer that was cached before if it is
+    // different, which means the solver regressed or the cached answer was wrong.
+    pub fn insert(&mut self, solver: &Solver, input: u64, answer: &Answer) -> Option<Answer> {
+        let entry = (version(solver), answer.clone());
+        let previous = self
+            .entries
+            .insert((solver.day, solver.part, input), entry.clone());
+
+        self.changed |= previous.as_ref() != Some(&entry);
+
+        previous
+            .map(|(_, answer)| answer)
+            .filter(|previous| previous != answer)
+    }
+
+    // Writes the cache back, if anything was added to it.
+    pub fn save(&mut self) -> Result<(), CacheError> {
+        if !self.changed {
+            return Ok(());
+        }
+
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(key, _)| **key);
+
+        let text = entries
+            .iter()
+            .map(|(key, entry)| format_line(**key, entry))
+            .collect::<String>();
+
+        let io_error = |source| CacheError::Io {
+            path: self.path.clone(),
+            source,
+        };
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+
+        std::fs::write(&self.path, text).map_err(io_error)?;
+        self.changed = false;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_line, input_hash, parse_line, Cache};
+    use crate::answer::Answer;
+    use crate::registry::{Solver, Status};
+
+    fn solver(source: &'static str) -> Solver {
+        Solver {
+            day: 3,
+            part: 2,
+            name: "day03::part2",
+            status: Status::Ok,
+            run: |_| Ok(Answer::from(0u8)),
+            phases: None,
+            stream: None,
+            source,
+        }
+    }
+
+    #[test]
+    fn answers_are_cached_per_version() {
+        let (old, new) = (solver("fn old() {}"), solver("fn new() {}"));
+        let input = input_hash("467..114..");
+        let mut cache = Cache::default();
+
+        assert_eq!(cache.insert(&old, input, &Answer::from(4361u32)), None);
+        assert_eq!(cache.get(&old, input), Some(&Answer::from(4361u32)));
+        assert_eq!(cache.get(&old, input_hash("something else")), None);
+        assert_eq!(cache.get(&new, input), None);
+
+        assert_eq!(
+            cache.insert(&new, input, &Answer::from(4360u32)),
+            Some(Answer::from(4361u32))
+        );
+        assert_eq!(cache.get(&new, input), Some(&Answer::from(4360u32)));
+        assert_eq!(cache.insert(&new, input, &Answer::from(4360u32)), None);
+    }
+
+    #[test]
+    fn answers_with_tabs_and_line_breaks_fit_on_a_line() {
+        let key = (7, 1, 0xabc);
+        let entry = (0xdef, Answer::from("a\tb\nc\\n"));
+        let line = format_line(key, &entry);
+
+        assert_eq!(line.lines().count(), 1);
+        assert_eq!(parse_line(line.trim_end_matches('\n')), Some((key, entry)));
+        assert_eq!(parse_line("7\t1\tabc\tdef\ta\\x"), None);
+    }
+
+    #[test]
+    fn input_hash_is_fnv1a() {
+        assert_eq!(input_hash(""), 0xcbf29ce484222325);
+        assert_eq!(input_hash("a"), 0xaf63dc4c8601ec8c);
+    }
+}
@@ -0,0 +1,144 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::aoc::{self, Hint, Reply};
+use crate::generate;
+use crate::input;
+use crate::registry::{self, Days, Status};
+use crate::run::print_outcome;
+use crate::runner::{self, Outcome};
+use crate::submissions;
+
+// Downloads the input of `day`, unless it is already there.
+pub fn fetch(input_dir: &Path, day: u8) -> Result<PathBuf, String> {
+    let path = input_dir.join(input::file_name(day));
+
+    // An empty file is only a placeholder.
+    if path.metadata().is_ok_and(|m| m.len() > 0) {
+        return Err(format!(
+            "the input of day {day} is already in {}, remove it to fetch it again",
+            path.display()
+        ));
+    }
+
+    let site = aoc::Site::from_env().map_err(|e| e.to_string())?;
+    let text = site.input(day).map_err(|e| e.to_string())?;
+
+    std::fs::create_dir_all(input_dir)
+        .and_then(|_| std::fs::write(&path, text))
+        .map_err(|e| format!("could not write {}: {e}", path.display()))?;
+
+    Ok(path)
+}
+
+// Sends the answer of a part, unless the submissions so far show that it is pointless.
+pub fn submit(
+    loader: &input::Loader,
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    limit: Option<Duration>,
+) -> Result<(), String> {
+    let answer = match answer {
+        Some(answer) => answer.trim().to_string(),
+        None => {
+            let Some(solver) = registry::select(Days::single(day), Some(part)).next() else {
+                return Err(format!("there is no solver for day {day} part {part} yet"));
+            };
+
+            // Unimplemented parts can give wrong answers, which are not worth a wait.
+            if solver.status == Status::Unimplemented {
+                return Err(format!(
+                    "day {day} part {part} is not implemented yet, pass --answer to submit anyway"
+                ));
+            }
+
+            let text = loader.load(day).map_err(|e| e.to_string())?;
+
+            match runner::execute_with_limit(solver, &Arc::from(text), limit) {
+                Outcome::Answer(answer) => answer.to_string(),
+                outcome => {
+                    print_outcome(solver, &outcome);
+                    return Err("there is no answer to submit".to_string());
+                }
+            }
+        }
+    };
+
+    let now = || {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    };
+
+    let path = aoc::state_dir().join("submissions.txt");
+    let mut log = submissions::Log::load(&path).map_err(|e| e.to_string())?;
+
+    if let Some(reason) = log.refusal(day, part, &answer, now()) {
+        return Err(format!("not submitting `{answer}`: {reason}"));
+    }
+
+    let site = aoc::Site::from_env().map_err(|e| e.to_string())?;
+
+    println!("Submitting `{answer}` for day {day} part {part}");
+    let reply = site.submit(day, part, &answer).map_err(|e| e.to_string())?;
+
+    let wait = match &reply {
+        Reply::Wrong { wait, .. } => wait.unwrap_or_default(),
+        Reply::TooSoon { wait } => *wait,
+        _ => Duration::ZERO,
+    };
+
+    log.record(submissions::Submission {
+        time: now(),
+        day,
+        part,
+        answer,
+        verdict: submissions::verdict(&reply).to_string(),
+        until: now() + wait.as_secs(),
+    })
+    .map_err(|e| e.to_string())?;
+
+    match reply {
+        Reply::Correct => println!("That is the right answer!"),
+        Reply::Wrong { hint, .. } => {
+            let hint = match hint {
+                Some(Hint::TooHigh) => ", it is too high",
+                Some(Hint::TooLow) => ", it is too low",
+                None => "",
+            };
+            println!("That is not the right answer{hint}");
+        }
+        Reply::TooSoon { .. } => {
+            println!("The answer was not checked, the last one was too recent")
+        }
+        Reply::WrongLevel => println!("This part is already solved, or the one before it is not"),
+        Reply::Unknown(text) => println!("Unexpected reply: {text}"),
+    }
+
+    if !wait.is_zero() {
+        println!("Wait {wait:?} before submitting again");
+    }
+
+    Ok(())
+}
+
+// Prints the input to stdout, so it can be redirected to a file, and the answers to stderr.
+pub fn gen(day: u8, seed: u64, size: usize) -> Result<(), String> {
+    let generated = generate::generate(day, seed, size)
+        .ok_or_else(|| format!("there is no generator for day {day}"))?;
+
+    print!("{}", generated.text);
+
+    for solver in registry::select(Days::single(day), None) {
+        let part = solver.part;
+        match &generated.answers[part as usize - 1] {
+            Some(answer) => eprintln!("Day {day}, part {part} = {answer}"),
+            None => eprintln!("Day {day}, part {part} is not known"),
+        }
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use crate::error::SolveError;
+use crate::util::Lines;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
@@ -14,23 +16,22 @@ fn try_digit(b: &u8) -> Option<u32> {
     }
 }
 
-pub fn part1(input: &str) -> u32 {
+pub fn part1(lines: &mut Lines) -> Result<u64, SolveError> {
     let mut answer = 0;
 
-    for line in input.lines() {
-        let a = line.as_bytes().iter().filter_map(try_digit).next().unwrap();
-        let b = line
-            .as_bytes()
-            .iter()
-            .rev()
-            .filter_map(try_digit)
-            .next()
-            .unwrap();
+    while let Some(line) = lines.next_line() {
+        let line = line?;
+        let a = line.as_bytes().iter().filter_map(try_digit).next();
+        let b = line.as_bytes().iter().rev().filter_map(try_digit).next();
 
-        answer += a * 10 + b;
+        let (Some(a), Some(b)) = (a, b) else {
+            return Err(SolveError::new(line, "line does not contain a digit"));
+        };
+
+        answer += (a * 10 + b) as u64;
     }
 
-    answer
+    Ok(answer)
 }
 
 fn written_number(input: &str) -> IResult<&str, u32> {
@@ -55,10 +56,11 @@ fn a_digit(input: &str) -> IResult<&str, u32> {
     )(input)
 }
 
-pub fn part2(input: &str) -> u32 {
+pub fn part2(lines: &mut Lines) -> Result<u64, SolveError> {
     let mut answer = 0;
 
-    for line in input.lines() {
+    while let Some(line) = lines.next_line() {
+        let line = line?;
         let a = 'search: {
             for start in 0..line.len() {
                 let res = alt((a_digit, written_number))(&line[start..]);
@@ -67,7 +69,7 @@ pub fn part2(input: &str) -> u32 {
                 }
             }
 
-            unreachable!();
+            return Err(SolveError::new(line, "line does not contain a digit"));
         };
 
         let b = 'search: {
@@ -78,11 +80,11 @@ pub fn part2(input: &str) -> u32 {
                 }
             }
 
-            unreachable!();
+            return Err(SolveError::new(line, "line does not contain a digit"));
         };
 
-        answer += a * 10 + b;
+        answer += (a * 10 + b) as u64;
     }
 
-    answer
+    Ok(answer)
 }
@@ -1,39 +1,54 @@
-pub fn part1(input: &str) -> u32 {
+use crate::error::SolveError;
+use crate::util::{parse, Lines};
+
+fn parse_grab(grab: &str) -> Result<(u32, &str), SolveError> {
+    // Skip the preceding space
+    let grab = grab.strip_prefix(' ').unwrap_or(grab);
+
+    let Some((amount, color)) = grab.split_once(' ') else {
+        return Err(SolveError::new(grab, "expected an amount and a color"));
+    };
+
+    Ok((parse(amount)?, color))
+}
+
+pub fn part1(lines: &mut Lines) -> Result<u64, SolveError> {
     let mut sum = 0;
+    let mut id = 0;
+
+    while let Some(line) = lines.next_line() {
+        let line = line?;
+        id += 1;
 
-    for (idx, line) in input.lines().enumerate() {
         let draws = line.rsplit(':').next().unwrap().split(';');
 
         'game: {
             for draw in draws {
                 for grab in draw.split(',') {
-                    // Skip the preceding space
-                    let grab = &grab[1..];
-
-                    let mut it = grab.split(' ');
-                    let amount = it.next().unwrap().parse::<u8>().unwrap();
-                    let color = it.next().unwrap();
+                    let (amount, color) = parse_grab(grab)?;
 
                     match color {
                         "red" if amount > 12 => break 'game,
                         "green" if amount > 13 => break 'game,
                         "blue" if amount > 14 => break 'game,
-                        _ => (),
+                        "red" | "green" | "blue" => (),
+                        _ => return Err(SolveError::new(color, "unknown color")),
                     }
                 }
             }
 
-            sum += idx as u32 + 1;
+            sum += id;
         }
     }
 
-    sum
+    Ok(sum)
 }
 
-pub fn part2(input: &str) -> u32 {
-    let mut sum: u32 = 0;
+pub fn part2(lines: &mut Lines) -> Result<u64, SolveError> {
+    let mut sum: u64 = 0;
 
-    for line in input.lines() {
+    while let Some(line) = lines.next_line() {
+        let line = line?;
         let draws = line.rsplit(':').next().unwrap().split(';');
 
         let mut red = 0;
@@ -42,24 +57,19 @@ pub fn part2(input: &str) -> u32 {
 
         for draw in draws {
             for grab in draw.split(',') {
-                // Skip the preceding space
-                let grab = &grab[1..];
-
-                let mut it = grab.split(' ');
-                let amount = it.next().unwrap().parse::<u32>().unwrap();
-                let color = it.next().unwrap();
+                let (amount, color) = parse_grab(grab)?;
 
                 match color {
                     "red" => red = red.max(amount),
                     "green" => green = green.max(amount),
                     "blue" => blue = blue.max(amount),
-                    _ => unreachable!(),
+                    _ => return Err(SolveError::new(color, "unknown color")),
                 }
             }
         }
 
-        sum += red * green * blue;
+        sum += (red * green * blue) as u64;
     }
 
-    sum
+    Ok(sum)
 }
@@ -1,22 +1,29 @@
+use crate::error::SolveError;
+use crate::util;
 use core::ops::Range;
 
-const SIZE: usize = 140;
-const MAXP: usize = SIZE * SIZE;
-
-fn neighbors(p: usize) -> [usize; 8] {
-    [
-        p.wrapping_sub(SIZE + 1),
-        p.wrapping_sub(SIZE),
-        p.wrapping_sub(SIZE - 1),
-        p.wrapping_sub(1),
-        p + 1,
-        p + SIZE - 1,
-        p + SIZE,
-        p + SIZE + 1,
-    ]
+// The positions around `(y, x)` that are on the schematic, as indices into a row major lookup.
+fn neighbors(
+    (y, x): (usize, usize),
+    (width, height): (usize, usize),
+) -> impl Iterator<Item = usize> {
+    let ys = y.saturating_sub(1)..(y + 2).min(height);
+    let xs = x.saturating_sub(1)..(x + 2).min(width);
+
+    ys.flat_map(move |ny| xs.clone().map(move |nx| (ny, nx)))
+        .filter(move |&p| p != (y, x))
+        .map(move |(ny, nx)| ny * width + nx)
 }
 
-pub fn part1(input: &str) -> u64 {
+// The symbols with their position, and the numbers with the row and columns they are in.
+pub struct Schematic {
+    width: usize,
+    height: usize,
+    symbols: Vec<(u8, usize, usize)>,
+    nums: Vec<(usize, Range<usize>, u64)>,
+}
+
+pub fn parse(input: &str) -> Result<Schematic, SolveError> {
     // Parse all the symbols
     // ---------------------
 
@@ -62,28 +69,43 @@ pub fn part1(input: &str) -> u64 {
 
             let end = last + 1;
 
-            let c = line[start..end].parse::<u64>().unwrap();
+            let c = util::parse::<u64>(&line[start..end])?;
 
             nums.push((i, start..end, c));
         }
     }
 
-    let mut lookup = vec![b'.'; SIZE * SIZE];
-    for (b, line_idx, x) in symbols {
-        let p = line_idx * SIZE + x;
+    let width = input.lines().map(str::len).max().unwrap_or(0);
+    let height = input.lines().count();
+
+    Ok(Schematic {
+        width,
+        height,
+        symbols,
+        nums,
+    })
+}
+
+pub fn part1(schematic: &Schematic) -> Result<u64, SolveError> {
+    let Schematic {
+        width,
+        height,
+        symbols,
+        nums,
+    } = schematic;
+    let size = (*width, *height);
+
+    let mut lookup = vec![b'.'; width * height];
+    for &(b, line_idx, x) in symbols {
+        let p = line_idx * width + x;
         lookup[p] = b;
     }
 
     let mut sum = 0;
 
     'outer: for (y, span, num) in nums {
-        for x in span {
-            let nump = y * SIZE + x;
-            for np in neighbors(nump) {
-                if np >= MAXP {
-                    continue;
-                }
-
+        for x in span.clone() {
+            for np in neighbors((*y, x), size) {
                 if lookup[np] != b'.' {
                     sum += num;
                     continue 'outer;
@@ -92,88 +114,40 @@ pub fn part1(input: &str) -> u64 {
         }
     }
 
-    sum
+    Ok(sum)
 }
 
-pub fn part2(input: &str) -> u64 {
-    // Parse all the symbols
-    // ---------------------
-
-    let mut symbols = Vec::<(u8, usize, usize)>::new();
-
-    for (i, line) in input.lines().enumerate() {
-        let line_symbols = line.as_bytes().iter().enumerate().filter_map(|(j, &b)| {
-            if !b.is_ascii_digit() && b != b'.' {
-                Some((b, i, j))
-            } else {
-                None
-            }
-        });
-
-        symbols.extend(line_symbols);
-    }
-
-    // Parse all the numbers
-    // ---------------------
-
-    let mut nums = Vec::<(usize, Range<usize>, u64)>::new();
-
-    for (i, line) in input.lines().enumerate() {
-        let mut chars = line
-            .chars()
-            .enumerate()
-            .filter_map(|(i, c)| c.is_ascii_digit().then_some(i))
-            .peekable();
-
-        while chars.peek().is_some() {
-            let start = chars.next().unwrap();
-
-            let mut last = start;
-            while let Some(x) = chars.peek() {
-                if x - last != 1 {
-                    break;
-                }
-
-                last = *x;
-
-                chars.next();
-            }
-
-            let end = last + 1;
-
-            let c = line[start..end].parse::<u64>().unwrap();
-
-            nums.push((i, start..end, c));
-        }
-    }
+pub fn part2(schematic: &Schematic) -> Result<u64, SolveError> {
+    let Schematic {
+        width,
+        height,
+        symbols,
+        nums,
+    } = schematic;
+    let size = (*width, *height);
 
     // Create lookup table for the numbers
     // -----------------------------------
 
-    let mut lookup = vec![0u64; SIZE * SIZE];
+    let mut lookup = vec![0u64; width * height];
     for (line_idx, span, num) in nums {
-        for x in span {
-            let p = line_idx * SIZE + x;
-            lookup[p] = num;
+        for x in span.clone() {
+            let p = line_idx * width + x;
+            lookup[p] = *num;
         }
     }
 
     let mut sum = 0;
     let mut buf = Vec::new();
 
-    for (b, y, x) in symbols {
+    for &(b, y, x) in symbols {
         if b != b'*' {
             continue;
         }
 
         buf.clear();
 
-        let symp = y * SIZE + x;
-        for p in neighbors(symp) {
-            if p >= MAXP {
-                continue;
-            }
-
+        for p in neighbors((y, x), size) {
             if lookup[p] != 0 {
                 // This is very ugly and technically even incorrect, but it worked :)
                 // It is possible that a gear is adjacent to two different numbers with
@@ -196,5 +170,5 @@ pub fn part2(input: &str) -> u64 {
         sum += buf.iter().product::<u64>();
     }
 
-    sum
+    Ok(sum)
 }
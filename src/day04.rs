@@ -1,89 +1,80 @@
-pub fn part1(input: &str) -> u32 {
-    let mut buf = Vec::new();
-    let mut sum = 0;
+use std::collections::VecDeque;
+
+use crate::error::SolveError;
+use crate::util::{parse, Lines};
+
+// Returns how many of the drawn numbers on the card are winning numbers. `buf` is scratch space
+// for the winning numbers.
+fn count_matches(line: &str, buf: &mut Vec<u32>) -> Result<usize, SolveError> {
+    buf.clear();
+
+    let Some((_, nums)) = line.split_once(':') else {
+        return Err(SolveError::missing_after(
+            line,
+            "expected `:` after the card id",
+        ));
+    };
+
+    let Some((winners, draws)) = nums.split_once(" | ") else {
+        return Err(SolveError::missing_after(
+            nums,
+            "expected ` | ` between the numbers",
+        ));
+    };
+
+    for num in winners.split_ascii_whitespace() {
+        buf.push(parse::<u32>(num)?);
+    }
+
+    let mut count = 0;
 
-    for line in input.lines() {
-        buf.clear();
-
-        let nums = line.split(':').nth(1).unwrap();
-        let mut sections = nums.split(" | ");
-        let winners = sections.next().unwrap();
-        let draws = sections.next().unwrap();
-
-        for num in winners.as_bytes().chunks(3) {
-            let x = std::str::from_utf8(num)
-                .unwrap()
-                .trim()
-                .parse::<u32>()
-                .unwrap();
-            buf.push(x);
+    for num in draws.split_ascii_whitespace() {
+        let draw = parse::<u32>(num)?;
+
+        if buf.contains(&draw) {
+            count += 1;
         }
+    }
 
-        let mut count = 0;
+    Ok(count)
+}
 
-        for num in draws.as_bytes().chunks(3) {
-            let draw = std::str::from_utf8(num)
-                .unwrap()
-                .trim()
-                .parse::<u32>()
-                .unwrap();
+pub fn part1(lines: &mut Lines) -> Result<u64, SolveError> {
+    let mut buf = Vec::new();
+    let mut sum = 0;
 
-            if buf.iter().any(|&x| x == draw) {
-                count += 1;
-            }
-        }
+    while let Some(line) = lines.next_line() {
+        let count = count_matches(line?, &mut buf)?;
 
         if count > 0 {
             sum += 1 << (count - 1);
         }
     }
 
-    sum
+    Ok(sum)
 }
 
-pub fn part2(input: &str) -> u32 {
+pub fn part2(lines: &mut Lines) -> Result<u64, SolveError> {
     let mut buf = Vec::new();
-    let mut card_count = vec![1; 202];
-
-    for (i, line) in input.lines().enumerate() {
-        buf.clear();
-
-        let nums = line.split(':').nth(1).unwrap();
-        let mut sections = nums.split(" | ");
-        let winners = sections.next().unwrap();
-        let draws = sections.next().unwrap();
-
-        for num in winners.as_bytes().chunks(3) {
-            let x = std::str::from_utf8(num)
-                .unwrap()
-                .trim()
-                .parse::<u32>()
-                .unwrap();
-            buf.push(x);
-        }
-
-        let mut count = 0;
+    // How many copies were won of the cards after the current one. Only the cards that can still
+    // be won are kept, so there is no limit on the number of cards.
+    let mut won = VecDeque::new();
+    let mut sum = 0;
 
-        for num in draws.as_bytes().chunks(3) {
-            let draw = std::str::from_utf8(num)
-                .unwrap()
-                .trim()
-                .parse::<u32>()
-                .unwrap();
+    while let Some(line) = lines.next_line() {
+        let count = count_matches(line?, &mut buf)?;
+        let copies = 1 + won.pop_front().unwrap_or(0);
 
-            if buf.iter().any(|&x| x == draw) {
-                count += 1;
-            }
-        }
+        sum += copies;
 
-        if count == 0 {
-            continue;
+        if won.len() < count {
+            won.resize(count, 0);
         }
 
-        for j in (i + 1)..(i + 1 + count) {
-            card_count[j] += card_count[i];
+        for x in won.iter_mut().take(count) {
+            *x += copies;
         }
     }
 
-    card_count.iter().sum::<u32>()
+    Ok(sum)
 }
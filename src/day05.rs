@@ -1,11 +1,16 @@
+use crate::error::SolveError;
+use crate::util::parse_whitespace_separated_items;
 use core::ops::Range;
 
-fn parse_nums(section: &str) -> Vec<u64> {
-    let start = section.bytes().position(|b| b == b':').unwrap();
-    let list = &section[start + 1..].trim();
-    list.split_ascii_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect()
+fn parse_nums(section: &str) -> Result<Vec<u64>, SolveError> {
+    let Some((_, list)) = section.split_once(':') else {
+        return Err(SolveError::missing_after(
+            section.lines().next().unwrap_or(section),
+            "expected `:` after the section name",
+        ));
+    };
+
+    parse_whitespace_separated_items(list)
 }
 
 #[derive(Debug)]
@@ -27,8 +32,17 @@ impl Entry {
     }
 }
 
-fn parse_map(section: &str) -> Vec<Entry> {
-    let mut entries = parse_nums(section)
+fn parse_map(section: &str) -> Result<Vec<Entry>, SolveError> {
+    let nums = parse_nums(section)?;
+
+    if nums.is_empty() || nums.len() % 3 != 0 {
+        return Err(SolveError::new(
+            section.lines().next().unwrap_or(section),
+            "expected a map with lines of three numbers",
+        ));
+    }
+
+    let mut entries = nums
         .chunks_exact(3)
         .map(|nums| {
             let &[start_dst, start_src, len] = nums else {
@@ -41,7 +55,7 @@ fn parse_map(section: &str) -> Vec<Entry> {
 
     entries.sort_by_key(|e| e.src.start);
 
-    entries
+    Ok(entries)
 }
 
 fn fill_gaps(map: Vec<Entry>) -> Vec<Entry> {
@@ -94,7 +108,8 @@ fn seed_to_location(maps: &[Vec<Entry>], seed: u64) -> u64 {
              }| start <= x,
         );
 
-        assert!(entry_idx != 0 && entry_idx != map.len());
+        // The first entry starts at 0, so there is always one that starts at or before `x`.
+        assert!(entry_idx != 0);
 
         let entry = &map[entry_idx - 1];
 
@@ -104,22 +119,25 @@ fn seed_to_location(maps: &[Vec<Entry>], seed: u64) -> u64 {
     x
 }
 
-pub fn part1(input: &str) -> u64 {
-    let sections = input.split("\n\n").collect::<Vec<_>>();
-    let seeds = parse_nums(sections[0]);
-    let maps = sections[1..]
+fn parse_maps(sections: &[&str]) -> Result<Vec<Vec<Entry>>, SolveError> {
+    sections
         .iter()
-        .map(|&s| parse_map(s))
-        .map(fill_gaps)
-        .collect::<Vec<_>>();
+        .map(|&s| parse_map(s).map(fill_gaps))
+        .collect()
+}
+
+pub fn part1(input: &str) -> Result<u64, SolveError> {
+    let sections = input.split("\n\n").collect::<Vec<_>>();
+    let seeds = parse_nums(sections[0])?;
+    let maps = parse_maps(&sections[1..])?;
 
     let answer = seeds
         .iter()
         .map(|&seed| seed_to_location(&maps, seed))
         .min()
-        .unwrap();
+        .ok_or_else(|| SolveError::missing_after(sections[0], "expected at least one seed"))?;
 
-    answer
+    Ok(answer)
 }
 
 fn map_range(map: &[Entry], range: &Range<u64>) -> Vec<Range<u64>> {
@@ -153,9 +171,16 @@ fn map_range(map: &[Entry], range: &Range<u64>) -> Vec<Range<u64>> {
     res
 }
 
-pub fn part2(input: &str) -> u64 {
+pub fn part2(input: &str) -> Result<u64, SolveError> {
     let sections = input.split("\n\n").collect::<Vec<_>>();
-    let seeds = parse_nums(sections[0]);
+    let seeds = parse_nums(sections[0])?;
+
+    if seeds.is_empty() || seeds.len() % 2 != 0 {
+        return Err(SolveError::new(
+            sections[0],
+            "expected pairs of seed range starts and lengths",
+        ));
+    }
 
     let seed_ranges = seeds
         .chunks_exact(2)
@@ -165,11 +190,7 @@ pub fn part2(input: &str) -> u64 {
         })
         .collect::<Vec<_>>();
 
-    let maps = sections[1..]
-        .iter()
-        .map(|&s| parse_map(s))
-        .map(fill_gaps)
-        .collect::<Vec<_>>();
+    let maps = parse_maps(&sections[1..])?;
 
     let mut last_ranges = seed_ranges;
     for map in maps {
@@ -180,5 +201,20 @@ pub fn part2(input: &str) -> u64 {
         last_ranges = out;
     }
 
-    last_ranges.iter().map(|r| r.start).min().unwrap()
+    // Empty seed ranges are dropped by the maps, which leaves nothing if they are all empty
+    last_ranges
+        .iter()
+        .map(|r| r.start)
+        .min()
+        .ok_or_else(|| SolveError::new(sections[0], "every seed range is empty"))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn empty_seed_ranges() {
+        let input = "seeds: 79 0 55 0\n\nseed-to-soil map:\n50 98 2\n";
+        assert_eq!(super::part1(input), Ok(0));
+        assert!(super::part2(input).is_err());
+    }
 }
@@ -1,4 +1,5 @@
-use crate::util::parse_whitespace_separated_items;
+use crate::error::SolveError;
+use crate::util::{parse, parse_whitespace_separated_items};
 
 fn quadratic_roots(a: f64, b: f64, c: f64) -> [f64; 2] {
     let d = b * b - 4.0 * a * c;
@@ -31,29 +32,56 @@ fn prev_whole_number(x: f64) -> f64 {
     }
 }
 
-pub fn part1(input: &str) -> u64 {
+// The numbers of the `Time:` or `Distance:` line.
+fn row<'a>(line: &'a str, name: &str) -> Result<&'a str, SolveError> {
+    line.strip_prefix(name)
+        .and_then(|s| s.strip_prefix(':'))
+        .ok_or_else(|| SolveError::new(line, format!("expected a `{name}:` line")))
+}
+
+fn rows(input: &str) -> Result<(&str, &str), SolveError> {
     let lines = input.lines().collect::<Vec<_>>();
-    let time = parse_whitespace_separated_items::<f64>(lines[0].split(':').nth(1).unwrap());
-    let distance = parse_whitespace_separated_items::<f64>(lines[1].split(':').nth(1).unwrap());
+    let &[time, distance, ..] = lines.as_slice() else {
+        return Err(SolveError::new(
+            input,
+            "expected a `Time:` and a `Distance:` line",
+        ));
+    };
 
-    let mut answer = 1u64;
-    for (t, d) in time.iter().zip(distance) {
-        let [x0, x1] = quadratic_roots(1.0, -t, d);
-        let a = next_whole_number(x0);
-        let b = prev_whole_number(x1);
-        let c = b - a + 1.0;
-        answer *= c as u64;
+    Ok((row(time, "Time")?, row(distance, "Distance")?))
+}
+
+// Part 2 reads the numbers of a line as one number, ignoring the spaces between them.
+fn kerned(nums: &str) -> Result<f64, SolveError> {
+    let mut n = 0u64;
+    for item in nums.split_ascii_whitespace() {
+        n = n * 10u64.pow(item.len() as u32) + parse::<u64>(item)?;
     }
 
-    answer
+    Ok(n as f64)
 }
 
-pub fn part2(_input: &str) -> u64 {
-    // TODO Parse the numbers from the input
-    let t = 48938595.0;
-    let d = 296192812361391.0;
+fn ways_to_win(t: f64, d: f64) -> u64 {
     let [x0, x1] = quadratic_roots(1.0, -t, d);
     let a = next_whole_number(x0);
     let b = prev_whole_number(x1);
     (b - a + 1.0) as u64
 }
+
+pub fn part1(input: &str) -> Result<u64, SolveError> {
+    let (time, distance) = rows(input)?;
+    let time = parse_whitespace_separated_items::<f64>(time)?;
+    let distance = parse_whitespace_separated_items::<f64>(distance)?;
+
+    let mut answer = 1u64;
+    for (t, d) in time.iter().zip(distance) {
+        answer *= ways_to_win(*t, d);
+    }
+
+    Ok(answer)
+}
+
+pub fn part2(input: &str) -> Result<u64, SolveError> {
+    let (time, distance) = rows(input)?;
+    Ok(ways_to_win(kerned(time)?, kerned(distance)?))
+}
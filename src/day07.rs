@@ -1,12 +1,15 @@
-fn card_value(c: u8) -> u32 {
+use crate::error::SolveError;
+use crate::util::parse;
+
+fn card_value(c: u8) -> Option<u32> {
     match c {
-        b'2'..=b'9' => (c - b'0') as u32,
-        b'T' => 10,
-        b'J' => 11,
-        b'Q' => 12,
-        b'K' => 13,
-        b'A' => 14,
-        _ => unreachable!(),
+        b'2'..=b'9' => Some((c - b'0') as u32),
+        b'T' => Some(10),
+        b'J' => Some(11),
+        b'Q' => Some(12),
+        b'K' => Some(13),
+        b'A' => Some(14),
+        _ => None,
     }
 }
 
@@ -42,14 +45,28 @@ fn determine_hand_type(distinct_card_count: u32, max_same_card_count: u32) -> Ha
     }
 }
 
-fn parse_entry(line: &str) -> (Hand, u64) {
-    let mut sections = line.split_ascii_whitespace();
-    let hand = sections.next().unwrap();
-    let bid = sections.next().unwrap().parse::<u64>().unwrap();
+// Splits a line into the cards of the hand, using `value` to rank each card, and the bid.
+fn parse_cards(line: &str, value: fn(u8) -> Option<u32>) -> Result<([u32; 5], u64), SolveError> {
+    let Some((hand, bid)) = line.split_once(' ') else {
+        return Err(SolveError::missing_after(line, "expected a hand and a bid"));
+    };
 
-    let dealt = <&[u8; 5]>::try_from(hand.as_bytes())
-        .unwrap()
-        .map(card_value);
+    let bid = parse::<u64>(bid.trim())?;
+
+    if hand.len() != 5 || !hand.is_ascii() {
+        return Err(SolveError::new(hand, "a hand must have exactly 5 cards"));
+    }
+
+    let mut dealt = [0; 5];
+    for (i, c) in hand.bytes().enumerate() {
+        dealt[i] = value(c).ok_or_else(|| SolveError::new(&hand[i..i + 1], "unknown card"))?;
+    }
+
+    Ok((dealt, bid))
+}
+
+fn parse_entry(line: &str) -> Result<(Hand, u64), SolveError> {
+    let (dealt, bid) = parse_cards(line, card_value)?;
 
     let mut counter = [0; 15];
     for i in dealt {
@@ -68,29 +85,34 @@ fn parse_entry(line: &str) -> (Hand, u64) {
 
     let ty = determine_hand_type(distinct_card_count, max_same_card_count);
 
-    (Hand { ty, dealt }, bid)
+    Ok((Hand { ty, dealt }, bid))
 }
 
-pub fn part1(input: &str) -> u64 {
-    let mut entries = input.lines().map(parse_entry).collect::<Vec<_>>();
+pub fn part1(input: &str) -> Result<u64, SolveError> {
+    let mut entries = input
+        .lines()
+        .map(parse_entry)
+        .collect::<Result<Vec<_>, _>>()?;
     entries.sort_unstable_by_key(|entry| entry.0);
 
-    entries
+    let answer = entries
         .iter()
         .enumerate()
         .map(|(i, (_, bid))| (i as u64 + 1) * bid)
-        .sum::<u64>()
+        .sum::<u64>();
+
+    Ok(answer)
 }
 
-fn card_value_with_joker(c: u8) -> u32 {
+fn card_value_with_joker(c: u8) -> Option<u32> {
     match c {
-        b'J' => 1,
-        b'2'..=b'9' => (c - b'0') as u32,
-        b'T' => 10,
-        b'Q' => 12,
-        b'K' => 13,
-        b'A' => 14,
-        _ => unreachable!(),
+        b'J' => Some(1),
+        b'2'..=b'9' => Some((c - b'0') as u32),
+        b'T' => Some(10),
+        b'Q' => Some(12),
+        b'K' => Some(13),
+        b'A' => Some(14),
+        _ => None,
     }
 }
 
@@ -118,14 +140,8 @@ fn determine_hand_type_with_jokers(
     }
 }
 
-fn parse_entry_part2(line: &str) -> (Hand, u64) {
-    let mut sections = line.split_ascii_whitespace();
-    let hand = sections.next().unwrap();
-    let bid = sections.next().unwrap().parse::<u64>().unwrap();
-
-    let dealt = <&[u8; 5]>::try_from(hand.as_bytes())
-        .unwrap()
-        .map(card_value_with_joker);
+fn parse_entry_part2(line: &str) -> Result<(Hand, u64), SolveError> {
+    let (dealt, bid) = parse_cards(line, card_value_with_joker)?;
 
     let mut counter = [0; 15];
     for i in dealt {
@@ -146,16 +162,21 @@ fn parse_entry_part2(line: &str) -> (Hand, u64) {
 
     let ty = determine_hand_type_with_jokers(distinct_card_count, max_same_card_count, joker_count);
 
-    (Hand { ty, dealt }, bid)
+    Ok((Hand { ty, dealt }, bid))
 }
 
-pub fn part2(input: &str) -> u64 {
-    let mut entries = input.lines().map(parse_entry_part2).collect::<Vec<_>>();
+pub fn part2(input: &str) -> Result<u64, SolveError> {
+    let mut entries = input
+        .lines()
+        .map(parse_entry_part2)
+        .collect::<Result<Vec<_>, _>>()?;
     entries.sort_unstable_by_key(|entry| entry.0);
 
-    entries
+    let answer = entries
         .iter()
         .enumerate()
         .map(|(i, (_, bid))| (i as u64 + 1) * bid)
-        .sum::<u64>()
+        .sum::<u64>();
+
+    Ok(answer)
 }
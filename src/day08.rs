@@ -1,31 +1,97 @@
+use crate::error::SolveError;
 use crate::util::lcm;
 
+// Labels are read as base 36 numbers with the last character as the most significant digit. The
+// letters come first, so that `A` is 0 and `Z` is 25 in every position.
+const BASE: u32 = 36;
+const LABELS: usize = (BASE * BASE * BASE) as usize;
+
 const START: u32 = 0;
-const END: u32 = 2 * (25 + 26 * (25 + (26 * 25)));
-
-fn parse_node(line: &str) -> (u32, u32, u32) {
-    let bs = line.as_bytes();
-    (
-        2 * ((bs[0] - b'A') as u32 + 26 * ((bs[1] - b'A') as u32 + (26 * (bs[2] - b'A') as u32))),
-        2 * ((bs[7] - b'A') as u32 + 26 * ((bs[8] - b'A') as u32 + (26 * (bs[9] - b'A') as u32))),
-        2 * ((bs[12] - b'A') as u32
-            + 26 * ((bs[13] - b'A') as u32 + (26 * (bs[14] - b'A') as u32))),
-    )
+const END: u32 = 2 * (25 + BASE * (25 + (BASE * 25)));
+
+fn parse_label(label: &str) -> Result<u32, SolveError> {
+    let bs = label.as_bytes();
+
+    if bs.len() != 3 || !bs.iter().all(u8::is_ascii_alphanumeric) {
+        return Err(SolveError::new(
+            label,
+            "expected a label of three letters or digits",
+        ));
+    }
+
+    let digit = |b: u8| match b {
+        b'0'..=b'9' => (b - b'0') as u32 + 26,
+        _ => (b.to_ascii_uppercase() - b'A') as u32,
+    };
+
+    Ok(2 * (digit(bs[0]) + BASE * (digit(bs[1]) + BASE * digit(bs[2]))))
+}
+
+// A node and its left and right neighbors.
+type Node = (u32, u32, u32);
+
+fn parse_node(line: &str) -> Result<Node, SolveError> {
+    let Some((from, to)) = line.split_once(" = ") else {
+        return Err(SolveError::missing_after(
+            line,
+            "expected ` = ` after the node",
+        ));
+    };
+
+    let Some((left, right)) = to
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .and_then(|s| s.split_once(", "))
+    else {
+        return Err(SolveError::new(to, "expected `(left, right)`"));
+    };
+
+    Ok((parse_label(from)?, parse_label(left)?, parse_label(right)?))
 }
 
-pub fn part1(input: &str) -> u32 {
-    let mut sections = input.split("\n\n");
-    let instructions = sections
-        .next()
-        .unwrap()
-        .as_bytes()
-        .iter()
-        .copied()
-        .map(|x| if x == b'L' { 0u32 } else { 1u32 })
-        .cycle();
-    let nodes = sections.next().unwrap().lines().map(parse_node);
-
-    let mut lookup = [0u32; 26 * 26 * 26 * 2];
+// Returns the instructions, where 0 means left and 1 means right, and all the nodes.
+fn parse(input: &str) -> Result<(Vec<u32>, Vec<Node>), SolveError> {
+    let Some((instructions, nodes)) = input.split_once("\n\n") else {
+        return Err(SolveError::missing_after(
+            input.lines().next().unwrap_or(input),
+            "expected the nodes after the instructions",
+        ));
+    };
+
+    let instructions = instructions
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(0u32),
+            'R' => Ok(1u32),
+            _ => Err(SolveError::new(
+                &instructions[i..i + c.len_utf8()],
+                "expected L or R",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if instructions.is_empty() {
+        return Err(SolveError::new(input, "expected at least one instruction"));
+    }
+
+    let nodes = nodes
+        .lines()
+        .map(parse_node)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((instructions, nodes))
+}
+
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    let (instructions, nodes) = parse(input)?;
+    let instructions = instructions.iter().copied().cycle();
+
+    // Every missing node leads back to the start, so without it we would walk forever.
+    if !nodes.iter().any(|&(from, _, _)| from == START) {
+        return Err(SolveError::new(input, "there is no node AAA to start from"));
+    }
+
+    let mut lookup = vec![0u32; LABELS * 2];
     for (from, left, right) in nodes {
         lookup[from as usize] = left;
         lookup[(from | 1) as usize] = right;
@@ -43,28 +109,20 @@ pub fn part1(input: &str) -> u32 {
         steps += 1;
     }
 
-    steps
+    Ok(steps)
 }
 
-pub fn part2(input: &str) -> i64 {
-    let mut sections = input.split("\n\n");
-    let instructions = sections
-        .next()
-        .unwrap()
-        .as_bytes()
-        .iter()
-        .copied()
-        .map(|x| if x == b'L' { 0u32 } else { 1u32 })
-        .cycle();
-    let nodes = sections.next().unwrap().lines().map(parse_node);
+pub fn part2(input: &str) -> Result<i64, SolveError> {
+    let (instructions, nodes) = parse(input)?;
+    let instructions = instructions.iter().copied().cycle();
 
     let mut ghosts = Vec::new();
-    let mut lookup = [0u32; 26 * 26 * 26 * 2];
+    let mut lookup = vec![0u32; LABELS * 2];
     for (from, left, right) in nodes {
         lookup[from as usize] = left;
         lookup[(from | 1) as usize] = right;
 
-        if (from / (2 * 26 * 26)) == 0 {
+        if (from / (2 * BASE * BASE)) == 0 {
             ghosts.push(from);
         }
     }
@@ -88,7 +146,7 @@ pub fn part2(input: &str) -> i64 {
 
         // Walk around with a ghost until we hit an exit node.
         for inst in insts {
-            if at / (2 * 26 * 26) == 25 {
+            if at / (2 * BASE * BASE) == 25 {
                 break;
             }
 
@@ -100,21 +158,21 @@ pub fn part2(input: &str) -> i64 {
         answer = lcm(answer, i as i64);
     }
 
-    answer
+    Ok(answer)
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn part1() {
-        assert_eq!(super::part1(include_str!("../input/day_08.txt")), 15989);
+        assert_eq!(super::part1(include_str!("../input/day_08.txt")), Ok(15989));
     }
 
     #[test]
     fn part2() {
         assert_eq!(
             super::part2(include_str!("../input/day_08.txt")),
-            13830919117339
+            Ok(13830919117339)
         );
     }
 }
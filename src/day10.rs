@@ -1,5 +1,5 @@
-const HEIGHT: usize = 140;
-const WIDTH: usize = 140;
+use crate::error::SolveError;
+use crate::util::grid_size;
 
 const LEFT: u8 = 0b0001;
 const DOWN: u8 = 0b0010;
@@ -28,27 +28,28 @@ fn opposite_direction(direction: u8) -> u8 {
     }
 }
 
-fn determine_start_directions(tiles: &[u8], start: usize) -> (u8, u8) {
+fn determine_start_directions(tiles: &[u8], width: usize, start: usize) -> Option<(u8, u8)> {
+    let height = tiles.len() / width;
     let mut i = 0;
-    let mut res = [0; 2];
+    let mut res = [0; 4];
 
-    if start / WIDTH > 0 {
-        let up = start - WIDTH;
+    if start / width > 0 {
+        let up = start - width;
         if tiles[up] & DOWN != 0 {
             res[i] = UP;
             i += 1;
         }
     }
 
-    if start / WIDTH < HEIGHT - 1 {
-        let down = start + WIDTH;
+    if start / width < height - 1 {
+        let down = start + width;
         if tiles[down] & UP != 0 {
             res[i] = DOWN;
             i += 1;
         }
     }
 
-    if start % WIDTH > 0 {
+    if !start.is_multiple_of(width) {
         let left = start - 1;
         if tiles[left] & RIGHT != 0 {
             res[i] = LEFT;
@@ -56,7 +57,7 @@ fn determine_start_directions(tiles: &[u8], start: usize) -> (u8, u8) {
         }
     }
 
-    if start % WIDTH < WIDTH - 1 {
+    if start % width < width - 1 {
         let right = start + 1;
         if tiles[right] & LEFT != 0 {
             res[i] = RIGHT;
@@ -64,17 +65,15 @@ fn determine_start_directions(tiles: &[u8], start: usize) -> (u8, u8) {
         }
     }
 
-    debug_assert_eq!(i, 2);
-
-    (res[0], res[1])
+    (i == 2).then_some((res[0], res[1]))
 }
 
-fn step(tiles: &[u8], position: usize, direction_to: u8) -> (usize, u8, u8) {
+fn step(tiles: &[u8], width: usize, position: usize, direction_to: u8) -> (usize, u8, u8) {
     let offset: isize = match direction_to {
         LEFT => -1,
-        DOWN => WIDTH as isize,
+        DOWN => width as isize,
         RIGHT => 1,
-        UP => -(WIDTH as isize),
+        UP => -(width as isize),
         _ => unreachable!(),
     };
 
@@ -85,28 +84,55 @@ fn step(tiles: &[u8], position: usize, direction_to: u8) -> (usize, u8, u8) {
     (position, direction_to, direction_from)
 }
 
-pub fn part1(input: &str) -> u32 {
-    let mut tiles = Vec::with_capacity(WIDTH * HEIGHT);
+struct Pipes {
+    tiles: Vec<u8>,
+    width: usize,
+    start: usize,
+    // The two directions the loop leaves the start tile in
+    start_directions: (u8, u8),
+}
+
+fn parse(input: &str) -> Result<Pipes, SolveError> {
+    let (width, height) = grid_size(input)?;
 
-    let lines = input.lines();
-    for line in lines.clone() {
+    let mut tiles = Vec::with_capacity(width * height);
+    let mut start = None;
+
+    for (y, line) in input.lines().enumerate() {
         let row = line.as_bytes().iter().copied().map(parse_tile);
         tiles.extend(row);
-    }
-
-    debug_assert_eq!(tiles.len(), WIDTH * HEIGHT);
 
-    let start = 'search: {
-        for (y, line) in lines.enumerate() {
-            if let Some(x) = line.bytes().position(|b| b == b'S') {
-                break 'search y * WIDTH + x;
-            }
+        if let Some(x) = line.bytes().position(|b| b == b'S') {
+            start = start.or(Some((y * width + x, &line[x..x + 1])));
         }
+    }
+
+    let Some((start, tile)) = start else {
+        return Err(SolveError::new(input, "there is no start tile `S`"));
+    };
 
-        unreachable!();
+    let Some(start_directions) = determine_start_directions(&tiles, width, start) else {
+        return Err(SolveError::new(
+            tile,
+            "the start tile must connect to exactly two pipes",
+        ));
     };
 
-    let (da, db) = determine_start_directions(&tiles, start);
+    Ok(Pipes {
+        tiles,
+        width,
+        start,
+        start_directions,
+    })
+}
+
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    let Pipes {
+        tiles,
+        width,
+        start,
+        start_directions: (da, db),
+    } = parse(input)?;
 
     let mut distance = 0;
     let (mut a, mut da) = (start, da);
@@ -116,7 +142,7 @@ pub fn part1(input: &str) -> u32 {
     // loop going in one direction and then take half the length.
 
     loop {
-        (a, da, _) = step(&tiles, a, da);
+        (a, da, _) = step(&tiles, width, a, da);
 
         if a == b {
             // This means that a and b will pass each other at this step.
@@ -125,40 +151,25 @@ pub fn part1(input: &str) -> u32 {
 
         distance += 1;
 
-        (b, db, _) = step(&tiles, b, db);
+        (b, db, _) = step(&tiles, width, b, db);
 
         if a == b {
             break;
         }
     }
 
-    distance
+    Ok(distance)
 }
 
-pub fn part2(input: &str) -> u32 {
-    let mut tiles = Vec::with_capacity(WIDTH * HEIGHT);
-
-    let lines = input.lines();
-    for line in lines.clone() {
-        let row = line.as_bytes().iter().copied().map(parse_tile);
-        tiles.extend(row);
-    }
-
-    debug_assert_eq!(tiles.len(), WIDTH * HEIGHT);
-
-    let start = 'search: {
-        for (y, line) in lines.enumerate() {
-            if let Some(x) = line.bytes().position(|b| b == b'S') {
-                break 'search y * WIDTH + x;
-            }
-        }
-
-        unreachable!();
-    };
-
-    let mut outline_tiles = vec![0i8; WIDTH * HEIGHT];
+pub fn part2(input: &str) -> Result<u32, SolveError> {
+    let Pipes {
+        mut tiles,
+        width,
+        start,
+        start_directions: ds,
+    } = parse(input)?;
 
-    let ds = determine_start_directions(&tiles, start);
+    let mut outline_tiles = vec![0i8; tiles.len()];
 
     let (mut p, mut dir_to, mut dir_from) = (start, ds.0, opposite_direction(ds.0));
     tiles[start] = ds.0 | ds.1;
@@ -170,7 +181,7 @@ pub fn part2(input: &str) -> u32 {
             _ => 2,
         };
 
-        (p, dir_to, dir_from) = step(&tiles, p, dir_to);
+        (p, dir_to, dir_from) = step(&tiles, width, p, dir_to);
 
         if p == start {
             break;
@@ -178,7 +189,7 @@ pub fn part2(input: &str) -> u32 {
     }
 
     let mut acc = 0;
-    for row in outline_tiles.chunks_exact(WIDTH) {
+    for row in outline_tiles.chunks_exact(width) {
         let mut winding: i8 = 0;
         let mut last = 0;
 
@@ -194,5 +205,5 @@ pub fn part2(input: &str) -> u32 {
         }
     }
 
-    acc
+    Ok(acc)
 }
@@ -1,3 +1,6 @@
+use crate::error::SolveError;
+use crate::util::grid_size;
+
 fn manhattan_distance_u32(x0: u32, y0: u32, x1: u32, y1: u32) -> u32 {
     x0.abs_diff(x1) + y0.abs_diff(y1)
 }
@@ -6,7 +9,9 @@ fn minmax_u32(a: u32, b: u32) -> [u32; 2] {
     [a.min(b), a.max(b)]
 }
 
-pub fn part1(input: &str) -> u32 {
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    grid_size(input)?;
+
     let image = input
         .lines()
         .map(|line| line.as_bytes())
@@ -40,7 +45,7 @@ pub fn part1(input: &str) -> u32 {
     }
 
     let mut answer = 0;
-    for i in 0..galaxies.len() - 1 {
+    for i in 0..galaxies.len().saturating_sub(1) {
         let (x0, y0) = galaxies[i];
 
         for (x1, y1) in &galaxies[i..] {
@@ -54,7 +59,7 @@ pub fn part1(input: &str) -> u32 {
         }
     }
 
-    answer
+    Ok(answer)
 }
 
 fn manhattan_distance_u64(x0: u64, y0: u64, x1: u64, y1: u64) -> u64 {
@@ -65,7 +70,9 @@ fn minmax_u64(a: u64, b: u64) -> [u64; 2] {
     [a.min(b), a.max(b)]
 }
 
-pub fn part2(input: &str) -> u64 {
+pub fn part2(input: &str) -> Result<u64, SolveError> {
+    grid_size(input)?;
+
     let image = input
         .lines()
         .map(|line| line.as_bytes())
@@ -99,7 +106,7 @@ pub fn part2(input: &str) -> u64 {
     }
 
     let mut answer = 0;
-    for i in 0..galaxies.len() - 1 {
+    for i in 0..galaxies.len().saturating_sub(1) {
         let (x0, y0) = galaxies[i];
 
         for (x1, y1) in &galaxies[i..] {
@@ -114,21 +121,24 @@ pub fn part2(input: &str) -> u64 {
         }
     }
 
-    answer
+    Ok(answer)
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn part1() {
-        assert_eq!(super::part1(include_str!("../input/day_11.txt")), 9648398);
+        assert_eq!(
+            super::part1(include_str!("../input/day_11.txt")),
+            Ok(9648398)
+        );
     }
 
     #[test]
     fn part2() {
         assert_eq!(
             super::part2(include_str!("../input/day_11.txt")),
-            618800410814
+            Ok(618800410814)
         );
     }
 }
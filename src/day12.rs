@@ -1,15 +1,18 @@
+use crate::error::SolveError;
+use crate::util::{parse, Lines};
 use ahash::AHashMap;
-use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
-use std::collections::HashMap;
-use std::iter;
 
 fn pack(record: u64, groups: u64) -> u64 {
     record | groups << 40
 }
 
-fn parse_line2(line: &str) -> u64 {
-    let mut sections = line.split_ascii_whitespace();
-    let record = sections.next().unwrap().as_bytes();
+// Packs the record and groups into a single number, if they are small enough. See the comment
+// below.
+fn pack_line(record: &[u8], groups: &[u8]) -> Option<u64> {
+    if record.len() > 20 || groups.len() > 6 || groups.iter().any(|&x| x > 15) {
+        return None;
+    }
+
     let record = record.iter().enumerate().fold(0u64, |acc, (i, c)| {
         let x = match c {
             b'.' => DOT,
@@ -20,17 +23,14 @@ fn parse_line2(line: &str) -> u64 {
         acc | (x << (2 * i))
     });
 
-    let groups = sections
-        .next()
-        .unwrap()
-        .split(',')
-        .map(|x| x.parse::<u8>().unwrap())
+    let groups = groups
+        .iter()
         .enumerate()
-        .fold(0u64, |acc, (i, x)| acc | ((x as u64) << (4 * i)));
+        .fold(0u64, |acc, (i, &x)| acc | ((x as u64) << (4 * i)));
 
     //println!("parse: record {:b}, groups {:b}", record, groups);
 
-    pack(record, groups)
+    Some(pack(record, groups))
 }
 
 // Biggest record had a length of 20
@@ -88,29 +88,49 @@ fn count2(memo: &mut AHashMap<u64, u64>, entry: u64) -> u64 {
 
     count
 }
-pub fn part1(input: &str) -> u64 {
+
+pub fn part1(lines: &mut Lines) -> Result<u64, SolveError> {
     let mut answer = 0;
-    for line in input.lines() {
-        answer += {
-            let mut memo = AHashMap::new();
-            let entry = parse_line2(line);
-            count2(&mut memo, entry)
+    while let Some(line) = lines.next_line() {
+        let (record, groups) = parse_line(line?)?;
+
+        // Records that are bigger than the ones in the puzzle input take the slower way
+        answer += match pack_line(record, &groups) {
+            Some(entry) => count2(&mut AHashMap::new(), entry),
+            None => count_arrangements(&mut AHashMap::new(), record, &groups),
         };
     }
 
-    answer
+    Ok(answer)
 }
 
-fn parse_line(line: &str) -> (&[u8], Vec<u8>) {
-    let mut sections = line.split_ascii_whitespace();
-    let record = sections.next().unwrap().as_bytes();
-    let groups = sections
-        .next()
-        .unwrap()
+fn parse_line(line: &str) -> Result<(&[u8], Vec<u8>), SolveError> {
+    let Some((record, groups)) = line.split_once(' ') else {
+        return Err(SolveError::missing_after(
+            line,
+            "expected a record and group sizes",
+        ));
+    };
+
+    if let Some((i, c)) = record
+        .char_indices()
+        .find(|(_, c)| !matches!(c, '.' | '#' | '?'))
+    {
+        return Err(SolveError::new(
+            &record[i..i + c.len_utf8()],
+            "expected `.`, `#` or `?`",
+        ));
+    }
+
+    let groups = groups
         .split(',')
-        .map(|x| x.parse::<u8>().unwrap())
-        .collect();
-    (record, groups)
+        .map(|x| match parse::<u8>(x)? {
+            0 => Err(SolveError::new(x, "group sizes must be at least 1")),
+            n => Ok(n),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((record.as_bytes(), groups))
 }
 
 fn count_arrangements<'a>(
@@ -151,7 +171,7 @@ fn count_arrangements<'a>(
                     .position(|&c| c != b'#')
                     .unwrap_or(record.len());
 
-                if (leading_hashtag_count as u8) == groups[0] {
+                if leading_hashtag_count == groups[0] as usize {
                     groups = &groups[1..];
                     let start = (leading_hashtag_count + 1).min(record.len());
                     record = &record[start..];
@@ -198,10 +218,13 @@ fn count_arrangements<'a>(
             break 'blk 0;
         }
 
-        for i in 1..(head as usize) {
-            if record[i] == b'.' {
-                break 'blk 0;
-            }
+        if record
+            .iter()
+            .take(head as usize)
+            .skip(1)
+            .any(|&c| c == b'.')
+        {
+            break 'blk 0;
         }
 
         if (head as usize) < record.len() && record[head as usize] == b'#' {
@@ -218,10 +241,10 @@ fn count_arrangements<'a>(
     *memo.entry((precord, pgroups)).or_insert(answer)
 }
 
-pub fn part2(input: &str) -> u64 {
+pub fn part2(lines: &mut Lines) -> Result<u64, SolveError> {
     let mut answer = 0;
-    for line in input.lines() {
-        let (record, mut groups) = parse_line(line);
+    while let Some(line) = lines.next_line() {
+        let (record, mut groups) = parse_line(line?)?;
 
         let mut record = record.to_vec();
 
@@ -238,21 +261,24 @@ pub fn part2(input: &str) -> u64 {
         answer += count_arrangements(&mut memo, &record, &groups);
     }
 
-    answer
+    Ok(answer)
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn part1() {
-        assert_eq!(super::part1(include_str!("../input/day_12.txt")), 7939);
+        assert_eq!(
+            super::part1(&mut include_str!("../input/day_12.txt").into()),
+            Ok(7939)
+        );
     }
 
     #[test]
     fn part2() {
         assert_eq!(
-            super::part2(include_str!("../input/day_12.txt")),
-            850504257483930
+            super::part2(&mut include_str!("../input/day_12.txt").into()),
+            Ok(850504257483930)
         );
     }
 }
@@ -1,9 +1,22 @@
+use crate::error::SolveError;
+use crate::util::grid_size;
+
 struct Bitmap {
     rows: Vec<u32>,
     cols: Vec<u32>,
 }
 
-fn parse_pattern(pattern: &str) -> Bitmap {
+fn parse_pattern(pattern: &str) -> Result<Bitmap, SolveError> {
+    let (width, height) = grid_size(pattern)?;
+
+    // Rows and columns are stored as bitmasks
+    if width > 32 || height > 32 {
+        return Err(SolveError::new(
+            pattern,
+            "a pattern can be at most 32 by 32 tiles",
+        ));
+    }
+
     let lines = pattern
         .lines()
         .map(|line| line.as_bytes())
@@ -32,7 +45,7 @@ fn parse_pattern(pattern: &str) -> Bitmap {
         })
         .collect::<Vec<u32>>();
 
-    Bitmap { rows, cols }
+    Ok(Bitmap { rows, cols })
 }
 
 fn count_reflections(lines: &[u32]) -> u32 {
@@ -47,17 +60,18 @@ fn count_reflections(lines: &[u32]) -> u32 {
     0
 }
 
-pub fn part1(input: &str) -> u32 {
+pub fn part1(input: &str) -> Result<u32, SolveError> {
     let patterns = input.split("\n\n");
 
     patterns
         .map(parse_pattern)
         .map(|bitmap| {
+            let bitmap = bitmap?;
             let a = count_reflections(&bitmap.cols);
             let b = count_reflections(&bitmap.rows);
-            a + 100 * b
+            Ok(a + 100 * b)
         })
-        .sum::<u32>()
+        .sum()
 }
 
 fn count_smudged_reflections(lines: &[u32]) -> u32 {
@@ -86,15 +100,16 @@ fn count_smudged_reflections(lines: &[u32]) -> u32 {
     0
 }
 
-pub fn part2(input: &str) -> u32 {
+pub fn part2(input: &str) -> Result<u32, SolveError> {
     let patterns = input.split("\n\n");
 
     patterns
         .map(parse_pattern)
         .map(|bitmap| {
+            let bitmap = bitmap?;
             let a = count_smudged_reflections(&bitmap.cols);
             let b = count_smudged_reflections(&bitmap.rows);
-            a + 100 * b
+            Ok(a + 100 * b)
         })
-        .sum::<u32>()
+        .sum()
 }
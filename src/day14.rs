@@ -1,4 +1,5 @@
-use std::collections::HashSet;
+use crate::error::SolveError;
+use crate::util::grid_size;
 
 #[derive(Clone, Hash, PartialEq, Eq)]
 struct Bitmap {
@@ -8,10 +9,9 @@ struct Bitmap {
 }
 
 impl Bitmap {
-    fn parse(input: &str) -> Bitmap {
+    fn parse(input: &str) -> Result<Bitmap, SolveError> {
+        let (width, height) = grid_size(input)?;
         let lines = input.lines().collect::<Vec<_>>();
-        let width = lines[0].len();
-        let height = lines.len();
 
         let data = lines
             .into_iter()
@@ -24,7 +24,7 @@ impl Bitmap {
             height,
         };
 
-        res.rotate_clockwise()
+        Ok(res.rotate_clockwise())
     }
 
     fn rotate_clockwise(&self) -> Bitmap {
@@ -94,12 +94,12 @@ impl Bitmap {
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    Bitmap::parse(input).tilt().total_load()
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    Ok(Bitmap::parse(input)?.tilt().total_load())
 }
 
-pub fn part2(input: &str) -> u32 {
-    let mut platform = Bitmap::parse(input);
+pub fn part2(input: &str) -> Result<u32, SolveError> {
+    let mut platform = Bitmap::parse(input)?;
     let mut history = Vec::new();
 
     let start = loop {
@@ -116,18 +116,21 @@ pub fn part2(input: &str) -> u32 {
 
     let period = history.len() - start;
     let offset = (999_999_999 - start) % period;
-    history[start + offset].total_load()
+    Ok(history[start + offset].total_load())
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn part1() {
-        assert_eq!(super::part1(include_str!("../input/day_14.txt")), 108857);
+        assert_eq!(
+            super::part1(include_str!("../input/day_14.txt")),
+            Ok(108857)
+        );
     }
 
     #[test]
     fn part2() {
-        assert_eq!(super::part2(include_str!("../input/day_14.txt")), 95273);
+        assert_eq!(super::part2(include_str!("../input/day_14.txt")), Ok(95273));
     }
 }
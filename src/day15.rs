@@ -1,3 +1,5 @@
+use crate::error::SolveError;
+
 fn xmas_hash(s: &[u8]) -> u8 {
     let mut acc = 0u32;
 
@@ -10,23 +12,31 @@ fn xmas_hash(s: &[u8]) -> u8 {
     acc as u8
 }
 
-pub fn part1(input: &str) -> u32 {
-    input
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    let answer = input
         .trim()
         .split(',')
         .map(|s| xmas_hash(s.as_bytes()) as u32)
-        .sum::<u32>()
+        .sum::<u32>();
+
+    Ok(answer)
 }
 
-pub fn part2(input: &str) -> u32 {
-    let mut boxes = std::iter::repeat(Vec::new()).take(256).collect::<Vec<_>>();
+pub fn part2(input: &str) -> Result<u32, SolveError> {
+    let mut boxes = std::iter::repeat_n(Vec::new(), 256).collect::<Vec<_>>();
 
-    for op in input.trim().split(',').map(|s| s.as_bytes()) {
+    for op in input.trim().split(',') {
         let (label, lens): (&[u8], u8) = {
-            if *op.last().unwrap() == b'-' {
-                (&op[..op.len() - 1], 0)
+            if let Some(label) = op.strip_suffix('-') {
+                (label.as_bytes(), 0)
+            } else if let Some((label, lens)) = op.split_once('=') {
+                let &[c @ b'1'..=b'9'] = lens.as_bytes() else {
+                    return Err(SolveError::new(lens, "expected a focal length from 1 to 9"));
+                };
+
+                (label.as_bytes(), c - b'0')
             } else {
-                (&op[..op.len() - 2], op[op.len() - 1] - b'0')
+                return Err(SolveError::new(op, "expected `label-` or `label=N`"));
             }
         };
 
@@ -52,5 +62,5 @@ pub fn part2(input: &str) -> u32 {
         }
     }
 
-    answer
+    Ok(answer)
 }
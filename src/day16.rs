@@ -1,3 +1,5 @@
+use crate::error::SolveError;
+use crate::util::grid_size;
 use ahash::{HashSet, HashSetExt};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -26,21 +28,20 @@ struct Bitmap {
 }
 
 impl Bitmap {
-    fn parse(input: &str) -> Bitmap {
+    fn parse(input: &str) -> Result<Bitmap, SolveError> {
+        let (width, height) = grid_size(input)?;
         let lines = input.lines().collect::<Vec<_>>();
-        let width = lines[0].len();
-        let height = lines.len();
 
         let data = lines
             .into_iter()
             .flat_map(|line| line.as_bytes().iter().copied())
             .collect();
 
-        Bitmap {
+        Ok(Bitmap {
             data,
             width,
             height,
-        }
+        })
     }
 
     fn get(&self, p: &Point) -> u8 {
@@ -109,13 +110,13 @@ fn count_energized_tiles(grid: &Bitmap, start: &(Point, Point)) -> u32 {
     unique_positions.len() as u32
 }
 
-pub fn part1(input: &str) -> u32 {
-    let grid = Bitmap::parse(input);
-    count_energized_tiles(&grid, &(Point::new(0, 0), RIGHT))
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    let grid = Bitmap::parse(input)?;
+    Ok(count_energized_tiles(&grid, &(Point::new(0, 0), RIGHT)))
 }
 
-pub fn part2(input: &str) -> u32 {
-    let grid = Bitmap::parse(input);
+pub fn part2(input: &str) -> Result<u32, SolveError> {
+    let grid = Bitmap::parse(input)?;
 
     let mut answer = 0;
 
@@ -141,5 +142,5 @@ pub fn part2(input: &str) -> u32 {
         answer = answer.max(e);
     }
 
-    answer
+    Ok(answer)
 }
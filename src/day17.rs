@@ -1,3 +1,6 @@
+use crate::error::SolveError;
+use crate::util::grid_size;
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Direction {
     Up,
@@ -43,7 +46,7 @@ impl Position {
     }
 }
 
-fn neighbors(c: Crucible, width: usize, height: usize) -> [Crucible; 3] {
+fn neighbors(c: Crucible, _width: usize, _height: usize) -> [Crucible; 3] {
     use Direction::*;
 
     let p = c.pos;
@@ -85,7 +88,6 @@ fn neighbors(c: Crucible, width: usize, height: usize) -> [Crucible; 3] {
             facing: Direction::Right,
             stability: 2,
         };
-        i += 1;
     } else {
         res[i] = Crucible {
             pos: Position { x: p.x, y: p.y - 1 },
@@ -99,13 +101,12 @@ fn neighbors(c: Crucible, width: usize, height: usize) -> [Crucible; 3] {
             facing: Direction::Down,
             stability: 2,
         };
-        i += 1;
     }
 
     res
 }
 
-fn neighbors_ultra(c: Crucible, width: usize, height: usize) -> [Crucible; 3] {
+fn neighbors_ultra(c: Crucible, _width: usize, _height: usize) -> [Crucible; 3] {
     use Direction::*;
 
     let p = c.pos;
@@ -152,7 +153,6 @@ fn neighbors_ultra(c: Crucible, width: usize, height: usize) -> [Crucible; 3] {
             facing: Direction::Right,
             stability: 9,
         };
-        i += 1;
     } else {
         res[i] = Crucible {
             pos: Position { x: p.x, y: p.y - 1 },
@@ -166,16 +166,17 @@ fn neighbors_ultra(c: Crucible, width: usize, height: usize) -> [Crucible; 3] {
             facing: Direction::Down,
             stability: 9,
         };
-        i += 1;
     }
 
     res
 }
 
+#[allow(dead_code)]
 fn manhattan(a: Position, b: Position) -> u32 {
     a.x.abs_diff(b.x) as u32 + a.y.abs_diff(b.y) as u32
 }
 
+#[allow(dead_code)]
 fn heuristic(p: Position, goal: Position) -> u32 {
     manhattan(p, goal)
 }
@@ -219,11 +220,10 @@ fn dijkstra<F: Fn(Crucible, usize, usize) -> [Crucible; 3], G: Fn(Crucible) -> b
             if score[nidx] == u32::MAX && !is_in_frontier {
                 score[nidx] = new_score;
 
-                if let Err(i) | Ok(i) = frontier
-                    .binary_search_by(|c| score[c.as_idx(width, stability_count)].cmp(&score[nidx]))
-                {
-                    frontier.insert(i, n);
-                }
+                let (Err(i) | Ok(i)) = frontier.binary_search_by(|c| {
+                    score[c.as_idx(width, stability_count)].cmp(&score[nidx])
+                });
+                frontier.insert(i, n);
             } else {
                 if is_in_frontier && new_score < score[nidx] {
                     let Some(frontier_idx) = frontier_idx else {
@@ -233,11 +233,10 @@ fn dijkstra<F: Fn(Crucible, usize, usize) -> [Crucible; 3], G: Fn(Crucible) -> b
                     frontier.remove(frontier_idx);
                     score[nidx] = new_score;
 
-                    if let Err(i) | Ok(i) = frontier.binary_search_by(|c| {
+                    let (Err(i) | Ok(i)) = frontier.binary_search_by(|c| {
                         score[c.as_idx(width, stability_count)].cmp(&score[nidx])
-                    }) {
-                        frontier.insert(i, n);
-                    }
+                    });
+                    frontier.insert(i, n);
                 }
             }
         }
@@ -302,26 +301,30 @@ fn astar(heatmap: &[u8], width: usize, height: usize) -> u32 {
 }
 */
 
-fn parse_heatmap(input: &str) -> (usize, usize, Vec<u8>) {
+fn parse_heatmap(input: &str) -> Result<(usize, usize, Vec<u8>), SolveError> {
+    let (width, height) = grid_size(input)?;
+
     let mut heatmap = Vec::new();
-    let mut h = 0;
-    for (i, line) in input.lines().enumerate() {
+    for line in input.lines() {
+        if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            return Err(SolveError::new(
+                &line[i..i + c.len_utf8()],
+                "expected a digit",
+            ));
+        }
+
         heatmap.extend(line.as_bytes());
-        h = i;
     }
 
     for p in heatmap.iter_mut() {
         *p -= b'0';
     }
 
-    let height = h + 1;
-    let width = heatmap.len() / height;
-
-    (width, height, heatmap)
+    Ok((width, height, heatmap))
 }
 
-pub fn part1(input: &str) -> u32 {
-    let (width, height, heatmap) = parse_heatmap(input);
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    let (width, height, heatmap) = parse_heatmap(input)?;
     let start = Crucible {
         pos: Position { x: 0, y: 0 },
         facing: Direction::Down,
@@ -333,11 +336,19 @@ pub fn part1(input: &str) -> u32 {
         y: height as isize - 1,
     };
 
-    dijkstra(&heatmap, width, height, neighbors, |c| c.pos == goal,&[start], 3)
+    Ok(dijkstra(
+        &heatmap,
+        width,
+        height,
+        neighbors,
+        |c| c.pos == goal,
+        &[start],
+        3,
+    ))
 }
 
-pub fn part2(input: &str) -> u32 {
-    let (width, height, heatmap) = parse_heatmap(input);
+pub fn part2(input: &str) -> Result<u32, SolveError> {
+    let (width, height, heatmap) = parse_heatmap(input)?;
     let start = [
         Crucible {
             pos: Position { x: 0, y: 0 },
@@ -356,5 +367,13 @@ pub fn part2(input: &str) -> u32 {
         y: height as isize - 1,
     };
 
-    dijkstra(&heatmap, width, height, neighbors_ultra, |c| c.pos == goal_pos && c.stability <= 6, &start, 10)
+    Ok(dijkstra(
+        &heatmap,
+        width,
+        height,
+        neighbors_ultra,
+        |c| c.pos == goal_pos && c.stability <= 6,
+        &start,
+        10,
+    ))
 }
@@ -1,3 +1,5 @@
+use crate::error::SolveError;
+use crate::util::parse;
 use core::ops::Range;
 
 #[derive(PartialEq, Eq, Debug)]
@@ -8,32 +10,36 @@ enum Direction {
     Left,
 }
 
-fn parse_line_part1(line: &str) -> (Direction, isize) {
+fn parse_line_part1(line: &str) -> Result<(Direction, isize), SolveError> {
     use Direction::*;
 
-    let bs = line.as_bytes();
-    let dir = match bs[0] {
-        b'U' => Up,
-        b'R' => Right,
-        b'D' => Down,
-        b'L' => Left,
-        _ => unreachable!(),
+    let mut sections = line.split_ascii_whitespace();
+
+    let dir = match sections.next() {
+        Some("U") => Up,
+        Some("R") => Right,
+        Some("D") => Down,
+        Some("L") => Left,
+        Some(x) => return Err(SolveError::new(x, "expected U, R, D or L")),
+        None => return Err(SolveError::new(line, "expected a direction")),
     };
 
-    let d = line
-        .split_ascii_whitespace()
-        .nth(1)
-        .unwrap()
-        .parse::<isize>()
-        .unwrap();
+    let Some(d) = sections.next() else {
+        return Err(SolveError::missing_after(line, "expected a distance"));
+    };
 
-    (dir, d as isize)
+    Ok((dir, parse::<isize>(d)?))
 }
 
-pub fn part1(input: &str) -> u32 {
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    let instructions = input
+        .lines()
+        .map(parse_line_part1)
+        .collect::<Result<Vec<_>, _>>()?;
+
     let mut corners = vec![(0isize, 0isize)];
 
-    for (dir, d) in input.lines().map(parse_line_part1) {
+    for (dir, d) in &instructions {
         let (x, y) = *corners.last().unwrap();
         use Direction::*;
         let p = match dir {
@@ -62,7 +68,7 @@ pub fn part1(input: &str) -> u32 {
 
     let mut bitmap = vec![0i8; width * height];
 
-    for ((dir, d), (x, y)) in input.lines().map(parse_line_part1).zip(corners.iter()) {
+    for ((dir, d), (x, y)) in instructions.into_iter().zip(corners.iter()) {
         if dir == Direction::Up {
             for dy in 0..=d {
                 let i = (x - minx) as usize + (y - miny - dy) as usize * width;
@@ -102,23 +108,40 @@ pub fn part1(input: &str) -> u32 {
         }
     }
 
-    answer
+    Ok(answer)
 }
 
-fn parse_line_part2(line: &str) -> (Direction, isize) {
-    let x = line.split_ascii_whitespace().nth(2).unwrap();
-    let d = isize::from_str_radix(&x[2..7], 16).unwrap();
+fn parse_line_part2(line: &str) -> Result<(Direction, isize), SolveError> {
+    let Some(x) = line.split_ascii_whitespace().nth(2) else {
+        return Err(SolveError::missing_after(line, "expected a color"));
+    };
+
+    // The color looks like `(#70c710)`
+    let Some(hex) = x
+        .strip_prefix("(#")
+        .and_then(|x| x.strip_suffix(')'))
+        .filter(|hex| hex.len() == 6 && hex.is_ascii())
+    else {
+        return Err(SolveError::new(x, "expected a color like `(#70c710)`"));
+    };
+
+    let Ok(d) = isize::from_str_radix(&hex[..5], 16) else {
+        return Err(SolveError::new(
+            &hex[..5],
+            "expected a hexadecimal distance",
+        ));
+    };
 
     use Direction::*;
-    let dir = match &x[7..8] {
+    let dir = match &hex[5..] {
         "0" => Right,
         "1" => Down,
         "2" => Left,
         "3" => Up,
-        _ => unreachable!(),
+        x => return Err(SolveError::new(x, "expected a direction from 0 to 3")),
     };
 
-    (dir, d)
+    Ok((dir, d))
 }
 
 fn overlap<Idx: Ord + Copy>(a: &Range<Idx>, b: &Range<Idx>) -> Range<Idx> {
@@ -129,8 +152,11 @@ fn overlap<Idx: Ord + Copy>(a: &Range<Idx>, b: &Range<Idx>) -> Range<Idx> {
     }
 }
 
-pub fn part2(input: &str) -> usize {
-    let instructions = input.lines().map(parse_line_part2);
+pub fn part2(input: &str) -> Result<usize, SolveError> {
+    let instructions = input
+        .lines()
+        .map(parse_line_part2)
+        .collect::<Result<Vec<_>, _>>()?;
 
     let mut ys = Vec::new();
     let mut segments = Vec::new();
@@ -199,12 +225,12 @@ pub fn part2(input: &str) -> usize {
     for window in xxs.as_slice().windows(2) {
         let [a, b] = window else { unreachable!() };
 
-        for x in a.as_slice().chunks_exact(2).map(|x| (*x[0]..(x[1] + 1))) {
-            for y in b.as_slice().chunks_exact(2).map(|x| (*x[0]..(x[1] + 1))) {
+        for x in a.as_slice().chunks_exact(2).map(|x| *x[0]..(x[1] + 1)) {
+            for y in b.as_slice().chunks_exact(2).map(|x| *x[0]..(x[1] + 1)) {
                 answer -= overlap(&x, &y).len();
             }
         }
     }
 
-    answer
+    Ok(answer)
 }
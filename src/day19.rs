@@ -1,3 +1,5 @@
+use crate::error::SolveError;
+use crate::util::parse;
 use std::collections::HashMap;
 
 #[derive(Clone, Copy)]
@@ -60,7 +62,7 @@ enum Rule<'a> {
 
 type Workflow<'a> = Vec<Rule<'a>>;
 
-fn parse_action(action: &str) -> Action {
+fn parse_action(action: &str) -> Action<'_> {
     match action {
         "A" => Action::Accept,
         "R" => Action::Reject,
@@ -68,11 +70,11 @@ fn parse_action(action: &str) -> Action {
     }
 }
 
-fn parse_rule(rule: &str) -> Rule {
+fn parse_rule(rule: &str) -> Result<Rule<'_>, SolveError> {
     let bs = rule.as_bytes();
 
     if rule.len() < 2 || !matches!(bs[1], b'>' | b'<') {
-        return Rule::ByDefault(parse_action(rule));
+        return Ok(Rule::ByDefault(parse_action(rule)));
     }
 
     let category = match bs[0] {
@@ -80,7 +82,7 @@ fn parse_rule(rule: &str) -> Rule {
         b'm' => 1,
         b'a' => 2,
         b's' => 3,
-        _ => unreachable!(),
+        _ => return Err(SolveError::new(&rule[..1], "expected x, m, a or s")),
     };
 
     let op = match bs[1] {
@@ -89,73 +91,99 @@ fn parse_rule(rule: &str) -> Rule {
         _ => unreachable!(),
     };
 
-    let mut parts = (&bs[2..]).split(|&c| c == b':');
-    let limit = std::str::from_utf8(parts.next().unwrap())
-        .unwrap()
-        .parse::<u32>()
-        .unwrap();
-    let action = parse_action(std::str::from_utf8(parts.next().unwrap()).unwrap());
+    let Some((limit, action)) = rule[2..].split_once(':') else {
+        return Err(SolveError::missing_after(
+            rule,
+            "expected `:` and an action",
+        ));
+    };
 
-    Rule::Condition(Conditional {
+    Ok(Rule::Condition(Conditional {
         category,
         op,
-        limit,
-        action,
-    })
+        limit: parse::<u32>(limit)?,
+        action: parse_action(action),
+    }))
 }
 
-fn parse_workflow(line: &str) -> (&str, Workflow) {
-    let mut parts = line.split('{');
-    let name = parts.next().unwrap();
-    let rules_text = {
-        let text = parts.next().unwrap();
-        &text[..text.len() - 1]
+fn parse_workflow(line: &str) -> Result<(&str, Workflow<'_>), SolveError> {
+    let Some((name, rules_text)) = line.split_once('{') else {
+        return Err(SolveError::missing_after(
+            line,
+            "expected `{` after the name",
+        ));
+    };
+
+    let Some(rules_text) = rules_text.strip_suffix('}') else {
+        return Err(SolveError::missing_after(rules_text, "expected `}`"));
     };
 
-    let rules = rules_text.split(',').map(parse_rule).collect::<Vec<_>>();
+    let rules = rules_text
+        .split(',')
+        .map(parse_rule)
+        .collect::<Result<Vec<_>, _>>()?;
 
-    (name, rules)
+    Ok((name, rules))
 }
 
-fn parse_part(line: &str) -> Part {
-    let bs = line.as_bytes();
-    let bs = &bs[1..bs.len() - 1];
+fn parse_workflows(section: &str) -> Result<HashMap<&str, Workflow<'_>>, SolveError> {
+    section.lines().map(parse_workflow).collect()
+}
+
+fn parse_part(line: &str) -> Result<Part, SolveError> {
+    let Some(ratings) = line.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+        return Err(SolveError::new(
+            line,
+            "expected a part like `{x=1,m=2,a=3,s=4}`",
+        ));
+    };
 
     let mut values = [0; 4];
 
-    for (i, category) in bs.split(|&c| c == b',').enumerate() {
-        let x = std::str::from_utf8(&category[2..])
-            .unwrap()
-            .parse::<u32>()
-            .unwrap();
-        values[i] = x;
+    for (i, category) in ratings.split(',').enumerate() {
+        if i >= values.len() {
+            return Err(SolveError::new(category, "a part only has four ratings"));
+        }
+
+        let Some((_, x)) = category.split_once('=') else {
+            return Err(SolveError::new(category, "expected `category=rating`"));
+        };
+
+        values[i] = parse::<u32>(x)?;
     }
 
-    values
+    Ok(values)
+}
+
+fn find_workflow<'a, 'b>(
+    workflows: &'b HashMap<&str, Workflow<'a>>,
+    label: &str,
+) -> Result<&'b Workflow<'a>, SolveError> {
+    workflows
+        .get(label)
+        .ok_or_else(|| SolveError::new(label, format!("there is no workflow named `{label}`")))
 }
 
-pub fn part1(input: &str) -> u64 {
-    let mut sections = input.split("\n\n");
+pub fn part1(input: &str) -> Result<u64, SolveError> {
+    let Some((workflows, parts)) = input.split_once("\n\n") else {
+        return Err(SolveError::missing_after(
+            input,
+            "expected the parts after the workflows",
+        ));
+    };
 
-    let workflows = sections
-        .next()
-        .unwrap()
-        .lines()
-        .map(parse_workflow)
-        .collect::<HashMap<_, _>>();
+    let workflows = parse_workflows(workflows)?;
 
-    let parts = sections
-        .next()
-        .unwrap()
+    let parts = parts
         .lines()
         .map(parse_part)
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
 
     let mut answer = 0;
     for part in parts {
         let mut current = "in";
         let accepted = 'check: loop {
-            let rules = workflows.get(current).unwrap();
+            let rules = find_workflow(&workflows, current)?;
             for rule in rules {
                 let action = match rule {
                     Rule::ByDefault(action) => Some(action),
@@ -195,20 +223,24 @@ pub fn part1(input: &str) -> u64 {
         }
     }
 
-    answer
+    Ok(answer)
 }
 
 fn part_configuration_count(part: &[Range; 4]) -> u64 {
     part.iter().map(|x| x.len() as u64).product::<u64>()
 }
 
-fn count_combinations(workflows: &HashMap<&str, Workflow>, label: &str, part: [Range; 4]) -> u64 {
+fn count_combinations(
+    workflows: &HashMap<&str, Workflow>,
+    label: &str,
+    part: [Range; 4],
+) -> Result<u64, SolveError> {
     if part_configuration_count(&part) == 0 {
-        return 0;
+        return Ok(0);
     }
 
     let mut count = 0;
-    let rules = workflows.get(label).unwrap();
+    let rules = find_workflow(workflows, label)?;
     let mut part = part;
     for rule in rules {
         use Action::*;
@@ -217,7 +249,7 @@ fn count_combinations(workflows: &HashMap<&str, Workflow>, label: &str, part: [R
         match rule {
             ByDefault(Accept) => count += part_configuration_count(&part),
             ByDefault(Reject) => (),
-            ByDefault(Jump(next)) => count += count_combinations(workflows, next, part),
+            ByDefault(Jump(next)) => count += count_combinations(workflows, next, part)?,
 
             Condition(Conditional {
                 category,
@@ -242,23 +274,17 @@ fn count_combinations(workflows: &HashMap<&str, Workflow>, label: &str, part: [R
                 match action {
                     Accept => count += part_configuration_count(&parts_if_matched),
                     Reject => (),
-                    Jump(next) => count += count_combinations(workflows, next, parts_if_matched),
+                    Jump(next) => count += count_combinations(workflows, next, parts_if_matched)?,
                 }
             }
         }
     }
 
-    count
+    Ok(count)
 }
 
-pub fn part2(input: &str) -> u64 {
-    let workflows = input
-        .split("\n\n")
-        .nth(0)
-        .unwrap()
-        .lines()
-        .map(parse_workflow)
-        .collect::<HashMap<_, _>>();
+pub fn part2(input: &str) -> Result<u64, SolveError> {
+    let workflows = parse_workflows(input.split("\n\n").next().unwrap())?;
 
     count_combinations(&workflows, "in", [Range::new(1, 4001); 4])
 }
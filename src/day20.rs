@@ -1,5 +1,5 @@
+use crate::error::SolveError;
 use std::collections::{HashMap, VecDeque};
-use std::mem::MaybeUninit;
 
 const PULSE_HI: i8 = 1;
 const PULSE_LO: i8 = -1;
@@ -26,6 +26,7 @@ enum ModuleKind {
 
 struct Module<'a> {
     kind: ModuleKind,
+    #[allow(dead_code)]
     id: &'a str,
     outputs: Range,
 }
@@ -141,102 +142,88 @@ impl State<'_> {
     }
 }
 
-fn parse_id(id: &str) -> u32 {
-    let bs = id.as_bytes();
-
-    debug_assert!(bs.len() <= 4);
-
-    let mut acc = 0u32;
-    for x in bs {
-        acc *= 256;
-        acc += *x as u32;
+fn parse_id(id: &str) -> Result<&str, SolveError> {
+    if id.is_empty() || !id.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err(SolveError::new(
+            id,
+            "expected a module name of letters and digits",
+        ));
     }
 
-    acc
+    Ok(id)
 }
 
-fn get_or_add_index(ids: &mut HashMap<u32, usize>, id: u32) -> usize {
+fn get_or_add_index<'a>(ids: &mut HashMap<&'a str, usize>, id: &'a str) -> usize {
     let n = ids.len();
     *ids.entry(id).or_insert(n)
 }
 
 fn parse_module<'a>(
-    ids: &mut HashMap<u32, usize>,
+    ids: &mut HashMap<&'a str, usize>,
     groups: &mut Vec<usize>,
     line: &'a str,
-) -> (usize, Module<'a>) {
-    let kind = &line[0..1];
-
-    if kind == "b" {
-        let start = groups.len();
-
-        let outputs = line.split(" -> ").nth(1).unwrap().split(", ").map(parse_id);
-
-        for id in outputs {
-            groups.push(get_or_add_index(ids, id));
-        }
-
-        let end = groups.len();
-
-        return (
-            0,
-            Module {
-                id: "broadcaster",
-                kind: ModuleKind::Broadcaster,
-                outputs: Range { start, end },
-            },
-        );
-    }
-
-    let kind = match kind {
-        "%" => ModuleKind::FlipFlop(PULSE_LO),
-        "&" => ModuleKind::Conjunction(Vec::new()),
-        _ => unreachable!(),
+) -> Result<(usize, Module<'a>), SolveError> {
+    let Some((name, outputs)) = line.split_once(" -> ") else {
+        return Err(SolveError::missing_after(
+            line,
+            "expected ` -> ` after the module",
+        ));
     };
 
-    let mut parts = line.split(" -> ");
-
-    let id_str = &parts.next().unwrap()[1..];
-    let idx = get_or_add_index(ids, parse_id(id_str));
+    let (idx, id_str, kind) = if name == "broadcaster" {
+        (0, name, ModuleKind::Broadcaster)
+    } else if let Some(id) = name.strip_prefix('%') {
+        let idx = get_or_add_index(ids, parse_id(id)?);
+        (idx, id, ModuleKind::FlipFlop(PULSE_LO))
+    } else if let Some(id) = name.strip_prefix('&') {
+        let idx = get_or_add_index(ids, parse_id(id)?);
+        (idx, id, ModuleKind::Conjunction(Vec::new()))
+    } else {
+        return Err(SolveError::new(
+            name,
+            "expected `broadcaster`, `%name` or `&name`",
+        ));
+    };
 
     let start = groups.len();
 
-    let outputs = parts.next().unwrap().split(", ").map(parse_id);
-
-    for id in outputs {
-        groups.push(get_or_add_index(ids, id));
+    for id in outputs.split(", ") {
+        groups.push(get_or_add_index(ids, parse_id(id)?));
     }
 
     let end = groups.len();
 
-    (
+    Ok((
         idx,
         Module {
             id: id_str,
             kind,
             outputs: Range { start, end },
         },
-    )
+    ))
 }
 
-fn parse_circuit(input: &str) -> State {
+fn parse_circuit(input: &str) -> Result<State<'_>, SolveError> {
     let mut ids = HashMap::new();
 
     let mut groups = Vec::new();
     let mut modules: Vec<Module> = Vec::new();
 
-    ids.insert(0, 0);
+    ids.insert("broadcaster", 0);
 
     for line in input.lines() {
-        let (idx, m) = parse_module(&mut ids, &mut groups, line);
+        let (idx, m) = parse_module(&mut ids, &mut groups, line)?;
 
         if modules.len() <= idx {
-            modules.resize_with(idx + 1, || Module::dummy());
+            modules.resize_with(idx + 1, Module::dummy);
         }
 
         modules[idx] = m;
     }
 
+    // Modules that only show up as an output, like `rx`, do nothing with the pulses they get.
+    modules.resize_with(ids.len(), Module::dummy);
+
     let n = modules.len();
 
     for conjunction_idx in 0..n {
@@ -260,11 +247,15 @@ fn parse_circuit(input: &str) -> State {
         }
     }
 
-    State { groups, modules }
+    if !matches!(modules.first(), Some(m) if matches!(m.kind, ModuleKind::Broadcaster)) {
+        return Err(SolveError::new(input, "there is no broadcaster module"));
+    }
+
+    Ok(State { groups, modules })
 }
 
-pub fn part1(input: &str) -> u64 {
-    let mut state = parse_circuit(input);
+pub fn part1(input: &str) -> Result<u64, SolveError> {
+    let mut state = parse_circuit(input)?;
 
     let mut lo_pulse_count = 0;
     let mut hi_pulse_count = 0;
@@ -275,20 +266,16 @@ pub fn part1(input: &str) -> u64 {
         hi_pulse_count += hi;
     }
 
-    lo_pulse_count * hi_pulse_count
+    Ok(lo_pulse_count * hi_pulse_count)
 }
 
-pub fn part2(input: &str) -> u64 {
-    let mut state = parse_circuit(input);
+pub fn part2(input: &str) -> Result<u64, SolveError> {
+    let mut state = parse_circuit(input)?;
     let mut answer = 1;
 
     while !state.push_button_part2() {
         answer += 1;
-
-        if answer % 100_000 == 0 {
-            println!("{answer}");
-        }
     }
 
-    answer
+    Ok(answer)
 }
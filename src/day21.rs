@@ -1,3 +1,5 @@
+use crate::error::SolveError;
+use crate::util::grid_size;
 use std::collections::VecDeque;
 
 #[derive(Clone, Copy)]
@@ -43,44 +45,36 @@ struct Garden {
     height: usize,
 }
 
-fn parse(input: &str) -> (Position, Garden) {
-    let lines = input.lines();
-    let mut height = 0;
+fn parse(input: &str) -> Result<(Position, Garden), SolveError> {
+    let (width, height) = grid_size(input)?;
     let mut plots = Vec::new();
 
-    for (i, line) in lines.enumerate() {
+    for line in input.lines() {
         plots.extend(line.as_bytes());
-        height = i;
     }
 
-    height += 1;
-    let width = plots.len() / height;
-    let mut start = 0;
+    let Some(start) = plots.iter().position(|&c| c == b'S') else {
+        return Err(SolveError::new(input, "there is no start tile `S`"));
+    };
 
-    for (i, c) in plots.iter_mut().enumerate() {
-        if *c == b'S' {
-            start = i;
-            *c = b'.';
-            break;
-        }
-    }
+    plots[start] = b'.';
 
     let start = Position {
         x: (start % width) as isize,
         y: (start / width) as isize,
     };
 
-    (
+    Ok((
         start,
         Garden {
             plots,
             width,
             height,
         },
-    )
+    ))
 }
 
-pub fn part1(input: &str) -> u32 {
+pub fn part1(input: &str) -> Result<u32, SolveError> {
     let (
         start,
         Garden {
@@ -88,7 +82,7 @@ pub fn part1(input: &str) -> u32 {
             width,
             height,
         },
-    ) = parse(input);
+    ) = parse(input)?;
 
     let mut ds = vec![u32::MAX; plots.len()];
 
@@ -120,7 +114,7 @@ pub fn part1(input: &str) -> u32 {
         }
     }
 
-    answer
+    Ok(answer)
 }
 
 fn count_plots(garden: &Garden, start: Position, max_distance: u32) -> u32 {
@@ -168,24 +162,24 @@ fn count_plots(garden: &Garden, start: Position, max_distance: u32) -> u32 {
 fn count_column_plots(garden: &Garden, start: Position, n: usize, max_steps: usize) -> u32 {
     let mut answer = 0;
 
-    answer += count_plots(&garden, start, (n / 2 + max_steps % n) as u32);
+    answer += count_plots(garden, start, (n / 2 + max_steps % n) as u32);
 
     if max_steps % n > n / 2 {
-        answer += count_plots(&garden, start, (max_steps % n - n / 2) as u32);
+        answer += count_plots(garden, start, (max_steps % n - n / 2) as u32);
     }
 
     answer
 }
 
-pub fn part2(input: &str) -> u64 {
+pub fn part2(input: &str) -> Result<u64, SolveError> {
     let (
-        start,
+        _start,
         ref garden @ Garden {
             ref plots,
             width,
             height,
         },
-    ) = parse(input);
+    ) = parse(input)?;
 
     debug_assert_eq!(width, height);
 
@@ -225,21 +219,19 @@ pub fn part2(input: &str) -> u64 {
 
     // Top
     let start = Position { x: (n / 2) as isize, y: (n - 1) as isize };
-    answer += dbg!(count_column_plots(&garden, start, n, max_steps) as u64);
+    answer += count_column_plots(garden, start, n, max_steps) as u64;
 
     // Bottom
     let start = Position { x: (n / 2) as isize, y: 0 };
-    answer += count_column_plots(&garden, start, n, max_steps) as u64;
+    answer += count_column_plots(garden, start, n, max_steps) as u64;
 
     // Right
-    let right = Position { x: 0, y: (n / 2) as isize };
-    answer += count_column_plots(&garden, start, n, max_steps) as u64;
+    let _right = Position { x: 0, y: (n / 2) as isize };
+    answer += count_column_plots(garden, start, n, max_steps) as u64;
 
     // Left
     let start = Position { x: (n - 1) as isize, y: (n / 2) as isize };
-    answer += count_column_plots(&garden, start, n, max_steps) as u64;
-
-    println!("{answer}");
+    answer += count_column_plots(garden, start, n, max_steps) as u64;
 
     // Diagonals
     // ---------
@@ -248,28 +240,28 @@ pub fn part2(input: &str) -> u64 {
     let s = n as u32;
 
     // Top right
-    answer += dbg!(count_plots(&garden, Position { x: 0, y: (n - 1) as isize }, s + r)) as u64 * m;
+    answer += count_plots(garden, Position { x: 0, y: (n - 1) as isize }, s + r) as u64 * m;
     if r > s / 2 {
-        answer += dbg!(count_plots(&garden, Position { x: 0, y: (n - 1) as isize }, r - s / 2)) as u64 * (m + 1);
+        answer += count_plots(garden, Position { x: 0, y: (n - 1) as isize }, r - s / 2) as u64 * (m + 1);
     }
 
     // Bottom right
-    answer += dbg!(count_plots(&garden, Position { x: 0, y: 0 }, s + r)) as u64 * m;
+    answer += count_plots(garden, Position { x: 0, y: 0 }, s + r) as u64 * m;
     if r > s / 2 {
-        answer += count_plots(&garden, Position { x: 0, y: 0 }, r - s / 2) as u64 * (m + 1);
+        answer += count_plots(garden, Position { x: 0, y: 0 }, r - s / 2) as u64 * (m + 1);
     }
 
     // Top left
-    answer += dbg!(count_plots(&garden, Position { x: (n - 1) as isize, y: (n - 1) as isize }, s + r)) as u64 * m;
+    answer += count_plots(garden, Position { x: (n - 1) as isize, y: (n - 1) as isize }, s + r) as u64 * m;
     if r > s / 2 {
-        answer += count_plots(&garden, Position { x: (n - 1) as isize, y: (n - 1) as isize }, r - s / 2) as u64 * (m + 1);
+        answer += count_plots(garden, Position { x: (n - 1) as isize, y: (n - 1) as isize }, r - s / 2) as u64 * (m + 1);
     }
 
     // Bottom left
-    answer += dbg!(count_plots(&garden, Position { x: (n - 1) as isize, y: 0 }, s + r)) as u64 * m;
+    answer += count_plots(garden, Position { x: (n - 1) as isize, y: 0 }, s + r) as u64 * m;
     if r > s / 2 {
-        answer += count_plots(&garden, Position { x: (n - 1) as isize, y: 0 }, r - s / 2) as u64 * (m + 1);
+        answer += count_plots(garden, Position { x: (n - 1) as isize, y: 0 }, r - s / 2) as u64 * (m + 1);
     }
 
-    answer
+    Ok(answer)
 }
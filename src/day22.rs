@@ -1,3 +1,6 @@
+use crate::error::SolveError;
+use crate::util;
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 struct Brick {
     pos: [u32; 3],
@@ -51,20 +54,34 @@ impl Iterator for Projector {
     }
 }
 
-fn parse_point(s: &str) -> [u32; 3] {
-    let mut numbers = s.split(',').map(|x| x.parse::<u32>().unwrap());
-    [
-        numbers.next().unwrap(),
-        numbers.next().unwrap(),
-        numbers.next().unwrap(),
-    ]
+fn parse_point(s: &str) -> Result<[u32; 3], SolveError> {
+    let mut numbers = s.split(',');
+    let mut point = [0; 3];
+
+    for x in point.iter_mut() {
+        let Some(number) = numbers.next() else {
+            return Err(SolveError::missing_after(
+                s,
+                "expected a point like `1,0,1`",
+            ));
+        };
+
+        *x = util::parse::<u32>(number)?;
+    }
+
+    Ok(point)
 }
 
-fn parse_brick(line: &str) -> Brick {
-    let mut points = line.split('~').map(parse_point);
+fn parse_brick(line: &str) -> Result<Brick, SolveError> {
+    let Some((a, b)) = line.split_once('~') else {
+        return Err(SolveError::missing_after(
+            line,
+            "expected `~` between the two ends",
+        ));
+    };
 
-    let a = points.next().unwrap();
-    let b = points.next().unwrap();
+    let a = parse_point(a)?;
+    let b = parse_point(b)?;
 
     let mut pos = [0; 3];
     let mut size = [0; 3];
@@ -73,32 +90,64 @@ fn parse_brick(line: &str) -> Brick {
         size[i] = a[i].abs_diff(b[i]);
     }
 
+    if size.iter().filter(|x| **x != 0).count() > 1 {
+        return Err(SolveError::new(
+            line,
+            "a brick can only extend in one direction",
+        ));
+    }
+
     let i = size.iter().position(|x| *x != 0).unwrap_or(2);
     size[i] += 1;
 
-    Brick { pos, size }
+    Ok(Brick { pos, size })
+}
+
+// The bricks sorted from low to high, and the size of the area they fall in.
+pub struct Snapshot {
+    bricks: Vec<Brick>,
+    width: usize,
+    height: usize,
 }
 
-pub fn part1(input: &str) -> u32 {
-    let mut bricks = input.lines().map(parse_brick).collect::<Vec<_>>();
+pub fn parse(input: &str) -> Result<Snapshot, SolveError> {
+    let mut bricks = input
+        .lines()
+        .map(parse_brick)
+        .collect::<Result<Vec<_>, _>>()?;
     bricks.sort_unstable_by_key(|b| b.pos[2]);
 
+    // The area has to fit the ends of the bricks, not only where they start
     let (width, height) = {
         let (min, max) = bricks
             .iter()
-            .map(|b| b.pos[0])
+            .map(|b| b.pos[0] + b.size[0].saturating_sub(1))
             .fold((0, 0), |(min, max), x| (min.min(x), max.max(x)));
         let width = max - min + 1;
 
         let (min, max) = bricks
             .iter()
-            .map(|b| b.pos[1])
+            .map(|b| b.pos[1] + b.size[1].saturating_sub(1))
             .fold((0, 0), |(min, max), y| (min.min(y), max.max(y)));
         let height = max - min + 1;
 
         (width as usize, height as usize)
     };
 
+    Ok(Snapshot {
+        bricks,
+        width,
+        height,
+    })
+}
+
+pub fn part1(snapshot: &Snapshot) -> Result<u32, SolveError> {
+    let &Snapshot {
+        ref bricks,
+        width,
+        height,
+    } = snapshot;
+
     let mut safe_to_remove = vec![true; bricks.len()];
     const NIL: usize = usize::MAX;
     let mut depths = vec![(0, NIL); width * height];
@@ -142,7 +191,7 @@ pub fn part1(input: &str) -> u32 {
         }
     }
 
-    count
+    Ok(count)
 }
 
 fn count_falling_bricks(removal: usize, supports: &[&[usize]]) -> u32 {
@@ -165,25 +214,12 @@ fn count_falling_bricks(removal: usize, supports: &[&[usize]]) -> u32 {
     count - 1
 }
 
-pub fn part2(input: &str) -> u64 {
-    let mut bricks = input.lines().map(parse_brick).collect::<Vec<_>>();
-    bricks.sort_unstable_by_key(|b| b.pos[2]);
-
-    let (width, height) = {
-        let (min, max) = bricks
-            .iter()
-            .map(|b| b.pos[0])
-            .fold((0, 0), |(min, max), x| (min.min(x), max.max(x)));
-        let width = max - min + 1;
-
-        let (min, max) = bricks
-            .iter()
-            .map(|b| b.pos[1])
-            .fold((0, 0), |(min, max), y| (min.min(y), max.max(y)));
-        let height = max - min + 1;
-
-        (width as usize, height as usize)
-    };
+pub fn part2(snapshot: &Snapshot) -> Result<u64, SolveError> {
+    let &Snapshot {
+        ref bricks,
+        width,
+        height,
+    } = snapshot;
 
     const NIL: usize = usize::MAX;
     let mut depths = vec![(0, NIL); width * height];
@@ -220,7 +256,23 @@ pub fn part2(input: &str) -> u64 {
     }
 
     let support_data = supports;
-    let supports = support_ranges.into_iter().map(|r| &support_data[r]).collect::<Vec<_>>();
+    let supports = support_ranges
+        .into_iter()
+        .map(|r| &support_data[r])
+        .collect::<Vec<_>>();
+
+    Ok((0..bricks.len())
+        .map(|i| count_falling_bricks(i, &supports) as u64)
+        .sum::<u64>())
+}
 
-    (0..bricks.len()).map(|i| count_falling_bricks(i, &supports) as u64).sum::<u64>()
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn bricks_extending_past_the_others_fit() {
+        // Both bricks reach further in x or y than where any brick starts
+        let snapshot = super::parse("0,0,1~2,0,1\n0,1,2~0,3,2\n").unwrap();
+        assert_eq!(super::part1(&snapshot), Ok(2));
+        assert_eq!(super::part2(&snapshot), Ok(0));
+    }
 }
@@ -1,3 +1,6 @@
+use crate::error::SolveError;
+use crate::util::grid_size;
+
 struct Map {
     tiles: Vec<u8>,
     width: usize,
@@ -11,6 +14,7 @@ struct Position {
 }
 
 impl Position {
+    #[allow(dead_code)]
     fn neighbors(&self) -> [Position; 4] {
         [
             Position {
@@ -66,23 +70,26 @@ impl Position {
     }
 }
 
-fn parse_map(input: &str) -> Map {
-    let mut height = 0;
+fn parse_map(input: &str) -> Result<Map, SolveError> {
+    let (width, height) = grid_size(input)?;
     let mut tiles = Vec::new();
 
-    for (i, line) in input.lines().enumerate() {
+    for line in input.lines() {
+        if let Some((i, c)) = line
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '#' | '.' | '>' | '<' | '^' | 'v'))
+        {
+            return Err(SolveError::new(&line[i..i + c.len_utf8()], "unknown tile"));
+        }
+
         tiles.extend(line.as_bytes());
-        height = i;
     }
 
-    height += 1;
-    let width = tiles.len() / height;
-
-    Map {
+    Ok(Map {
         tiles,
         width,
         height,
-    }
+    })
 }
 
 struct State {
@@ -214,12 +221,12 @@ fn search(
     answer
 }
 
-pub fn part1(input: &str) -> u32 {
+pub fn part1(input: &str) -> Result<u32, SolveError> {
     let Map {
         tiles,
         width,
         height,
-    } = parse_map(input);
+    } = parse_map(input)?;
 
     let visited = vec![false; width * height];
     let goal = (Position {
@@ -228,7 +235,7 @@ pub fn part1(input: &str) -> u32 {
     })
     .as_index(width);
 
-    search(
+    Ok(search(
         Position { x: 1, y: 0 },
         0,
         visited,
@@ -236,7 +243,7 @@ pub fn part1(input: &str) -> u32 {
         height,
         &tiles,
         goal,
-    )
+    ))
 }
 
 fn search2(
@@ -342,12 +349,12 @@ fn search2(
     answer
 }
 
-pub fn part2(input: &str) -> u32 {
+pub fn part2(input: &str) -> Result<u32, SolveError> {
     let Map {
         tiles,
         width,
         height,
-    } = parse_map(input);
+    } = parse_map(input)?;
 
     let visited = vec![false; width * height];
     let goal = (Position {
@@ -356,7 +363,7 @@ pub fn part2(input: &str) -> u32 {
     })
     .as_index(width);
 
-    search2(
+    Ok(search2(
         Position { x: 1, y: 0 },
         0,
         visited,
@@ -364,5 +371,5 @@ pub fn part2(input: &str) -> u32 {
         height,
         &tiles,
         goal,
-    )
+    ))
 }
@@ -1,34 +1,38 @@
-fn parse_line(line: &str) -> ([f64; 3], [f64; 3]) {
-    let mut parts = line.split(" @ ");
+use crate::error::SolveError;
+use crate::util::parse;
 
-    let mut ps = parts
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|p| p.trim().parse::<i64>().unwrap() as f64);
+fn parse_vector(s: &str) -> Result<[i64; 3], SolveError> {
+    let mut xs = s.split(',');
+    let mut res = [0; 3];
 
-    let pos = [ps.next().unwrap(), ps.next().unwrap(), ps.next().unwrap()];
+    for x in res.iter_mut() {
+        let Some(number) = xs.next() else {
+            return Err(SolveError::missing_after(s, "expected three numbers"));
+        };
 
-    let mut vs = parts
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|v| v.trim().parse::<i64>().unwrap() as f64);
+        *x = parse::<i64>(number.trim())?;
+    }
 
-    let vel = [vs.next().unwrap(), vs.next().unwrap(), vs.next().unwrap()];
+    Ok(res)
+}
 
-    (pos, vel)
+fn parse_line(line: &str) -> Result<([f64; 3], [f64; 3]), SolveError> {
+    let (pos, vel) = parse_line_part2(line)?;
+    Ok((pos.map(|x| x as f64), vel.map(|x| x as f64)))
 }
 
-pub fn part1(input: &str) -> u32 {
-    let stones = input.lines().map(parse_line).collect::<Vec<_>>();
+pub fn part1(input: &str) -> Result<u32, SolveError> {
+    let stones = input
+        .lines()
+        .map(parse_line)
+        .collect::<Result<Vec<_>, _>>()?;
 
     let lo = 200000000000000.0;
     let hi = 400000000000000.0;
 
     let mut answer = 0;
 
-    for (i, a) in stones[..(stones.len() - 1)].iter().enumerate() {
+    for (i, a) in stones[..stones.len().saturating_sub(1)].iter().enumerate() {
         for b in &stones[(i + 1)..] {
             let ([ax, ay, _], [avx, avy, _]) = a;
             let ([bx, by, _], [bvx, bvy, _]) = b;
@@ -56,29 +60,21 @@ pub fn part1(input: &str) -> u32 {
         }
     }
 
-    answer
+    Ok(answer)
 }
 
-fn parse_line_part2(line: &str) -> ([i64; 3], [i64; 3]) {
-    let mut parts = line.split(" @ ");
-
-    let mut ps = parts
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|p| p.trim().parse::<i64>().unwrap());
-
-    let pos = [ps.next().unwrap(), ps.next().unwrap(), ps.next().unwrap()];
+// Times at which part 2 looks where the second stone is, so far.
+const TIMES: i64 = 1000;
 
-    let mut vs = parts
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|v| v.trim().parse::<i64>().unwrap());
+fn parse_line_part2(line: &str) -> Result<([i64; 3], [i64; 3]), SolveError> {
+    let Some((pos, vel)) = line.split_once('@') else {
+        return Err(SolveError::missing_after(
+            line,
+            "expected `@` between position and velocity",
+        ));
+    };
 
-    let vel = [vs.next().unwrap(), vs.next().unwrap(), vs.next().unwrap()];
-
-    (pos, vel)
+    Ok((parse_vector(pos)?, parse_vector(vel)?))
 }
 
 fn dot(a: [i64; 3], b: [i64; 3]) -> i64 {
@@ -93,8 +89,13 @@ fn cross(a: [i64; 3], b: [i64; 3]) -> [i64; 3] {
     ]
 }
 
-pub fn part2(input: &str) -> u32 {
-    let mut stones = input.lines().map(parse_line_part2).collect::<Vec<_>>();
+// Work in progress, nothing is done with the determinants yet.
+#[allow(unused_variables)]
+pub fn part2(input: &str) -> Result<u32, SolveError> {
+    let mut stones = input
+        .lines()
+        .map(parse_line_part2)
+        .collect::<Result<Vec<_>, _>>()?;
 
     let (p0, p01) = stones[0];
 
@@ -108,10 +109,7 @@ pub fn part2(input: &str) -> u32 {
         ];
     }
 
-    println!("{stones:?}");
-
-    let mut t = 0;
-    loop {
+    for t in 0..TIMES {
         let (b1, h1) = stones[1];
 
         let p2 = [
@@ -135,10 +133,7 @@ pub fn part2(input: &str) -> u32 {
             let minus_lab = [-lab[0], -lab[1], -lab[2]];
 
             let det = dot(minus_lab, c);
-            println!("{det}");
         }
-
-        t += 1;
     }
 
     /*
@@ -0,0 +1,137 @@
+use ahash::AHashMap;
+
+use crate::error::SolveError;
+use crate::graph::Graph;
+
+// The puzzle promises that disconnecting this many wires splits the components in two.
+const WIRES: u64 = 3;
+
+// The vertex of the component called `name`, which is added the first time it is seen.
+fn component<'a>(
+    graph: &mut Graph,
+    ids: &mut AHashMap<&'a str, usize>,
+    name: &'a str,
+) -> Result<usize, SolveError> {
+    if name.is_empty() || !name.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err(SolveError::new(
+            name,
+            "expected a name of letters or digits",
+        ));
+    }
+
+    Ok(*ids.entry(name).or_insert_with(|| graph.add_vertex()))
+}
+
+// The components as a graph with a vertex per component and an edge per wire.
+pub struct Wiring {
+    pub graph: Graph,
+    // The name of every vertex
+    pub names: Vec<String>,
+}
+
+// Every line lists a component and the components it is connected to, like `jqt: rhn xhk nvd`.
+pub fn parse(input: &str) -> Result<Wiring, SolveError> {
+    let mut graph = Graph::new(0);
+    let mut ids = AHashMap::new();
+
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        let Some((from, to)) = line.split_once(": ") else {
+            return Err(SolveError::missing_after(
+                line,
+                "expected `: ` after the component",
+            ));
+        };
+
+        let from = component(&mut graph, &mut ids, from)?;
+
+        for name in to.split(' ') {
+            let to = component(&mut graph, &mut ids, name)?;
+            if from == to {
+                return Err(SolveError::new(name, "a component is connected to itself"));
+            }
+
+            graph.add_edge(from, to, 1);
+        }
+    }
+
+    if graph.len() < 2 {
+        return Err(SolveError::new(
+            input,
+            "expected at least two components to split",
+        ));
+    }
+
+    let mut names = vec![String::new(); graph.len()];
+    for (name, id) in ids {
+        names[id] = name.to_string();
+    }
+
+    Ok(Wiring { graph, names })
+}
+
+// The wires to disconnect and how many components end up on either side.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Split {
+    // The names of the components at the ends of every wire, in order
+    pub edges: Vec<(String, String)>,
+    pub sizes: (usize, usize),
+}
+
+pub fn split(wiring: &Wiring) -> Result<Split, SolveError> {
+    let cut = wiring
+        .graph
+        .min_cut()
+        .expect("parse checks for two components");
+
+    if cut.weight != WIRES {
+        return Err(SolveError::new(
+            "",
+            format!(
+                "expected to split the components by disconnecting {WIRES} wires, but it takes {}",
+                cut.weight
+            ),
+        ));
+    }
+
+    let mut edges = cut
+        .edges
+        .into_iter()
+        .map(|(a, b)| {
+            let (a, b) = (&wiring.names[a], &wiring.names[b]);
+            (a.min(b).clone(), a.max(b).clone())
+        })
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+
+    Ok(Split {
+        edges,
+        sizes: cut.sizes,
+    })
+}
+
+pub fn part1(wiring: &Wiring) -> Result<usize, SolveError> {
+    let Split { sizes, .. } = split(wiring)?;
+    Ok(sizes.0 * sizes.1)
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = include_str!("../input/examples/day_25_1.txt");
+
+    #[test]
+    fn part1() {
+        let wiring = super::parse(EXAMPLE).unwrap();
+        assert_eq!(super::part1(&wiring), Ok(54));
+
+        let cut = super::split(&wiring).unwrap();
+        let edges = cut
+            .edges
+            .iter()
+            .map(|(a, b)| (a.as_str(), b.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(edges, [("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")]);
+        assert_eq!(cut.sizes.0 + cut.sizes.1, 15);
+
+        assert!(super::parse("").is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+// Something in the puzzle input that a solver could not make sense of.
+//
+// Solvers create these with `SolveError::new` from a slice of their input. The runner then calls
+// `locate` with the complete input to fill in the day and where in the input the slice was found,
+// similar to how `nom::Offset` finds a slice in its parent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolveError {
+    pub day: u8,
+    // 1-based line and column of `text`, or 0 if it was not found in the input.
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+    source_line: String,
+    address: usize,
+}
+
+impl SolveError {
+    pub fn new(text: &str, message: impl Into<String>) -> SolveError {
+        SolveError {
+            day: 0,
+            line: 0,
+            column: 0,
+            text: text.to_string(),
+            message: message.into(),
+            source_line: String::new(),
+            address: text.as_ptr() as usize,
+        }
+    }
+
+    // Error for something that should have come after `text`, but is missing.
+    pub fn missing_after(text: &str, message: impl Into<String>) -> SolveError {
+        SolveError::new(&text[text.len()..], message)
+    }
+
+    pub fn locate(mut self, day: u8, input: &str) -> SolveError {
+        self.day = day;
+
+        let start = input.as_ptr() as usize;
+        if self.address < start || self.address > start + input.len() {
+            return self;
+        }
+
+        let offset = self.address - start;
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        self.line = input[..line_start].matches('\n').count() + 1;
+        self.column = input[line_start..offset].chars().count() + 1;
+        self.source_line = input[line_start..line_end]
+            .trim_end_matches('\r')
+            .to_string();
+
+        self
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            return write!(f, "day {}: {}", self.day, self.message);
+        }
+
+        writeln!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+
+        let gutter = self.line.to_string().len();
+        let underline = self
+            .text
+            .lines()
+            .next()
+            .unwrap_or("")
+            .chars()
+            .count()
+            .max(1);
+
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{:gutter$} | {:>column$}{}",
+            "",
+            "^",
+            "^".repeat(underline - 1),
+            column = self.column
+        )
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::SolveError;
+
+    #[test]
+    fn locate_finds_line_and_column() {
+        let input = "32T3K 765\nT55J5 abc\n";
+        let text = &input[16..19];
+
+        let e = SolveError::new(text, "expected a number").locate(7, input);
+
+        assert_eq!((e.day, e.line, e.column), (7, 2, 7));
+        assert_eq!(e.text, "abc");
+        assert_eq!(
+            e.to_string(),
+            "day 7, line 2, column 7: expected a number\n  |\n2 | T55J5 abc\n  |       ^^^"
+        );
+    }
+
+    #[test]
+    fn locate_ignores_text_from_elsewhere() {
+        let e = SolveError::new("nope", "bad").locate(3, "some input");
+        assert_eq!((e.day, e.line, e.column), (3, 0, 0));
+        assert_eq!(e.to_string(), "day 3: bad");
+    }
+}
@@ -0,0 +1,2260 @@
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+
+use crate::answer::Answer;
+use crate::util::{lcm, Rng};
+
+// The size that makes inputs which are quick to solve by brute force.
+pub const DEFAULT_SIZE: usize = 10;
+
+// A made up puzzle input, with the answers that were found while making it. These do not come from
+// the solvers, so they can be used to check them.
+pub struct Generated {
+    pub text: String,
+    // `None` for the parts where finding the answer without a clever solution takes too long
+    pub answers: [Option<Answer>; 2],
+}
+
+impl Generated {
+    fn new(text: String, part1: impl Into<Answer>, part2: impl Into<Answer>) -> Generated {
+        Generated {
+            text,
+            answers: [Some(part1.into()), Some(part2.into())],
+        }
+    }
+}
+
+// An input for `day` that looks like the puzzle input, made from `seed`. The same seed always gives
+// the same input. `size` is roughly the number of lines or the width of the grid divided by 10,
+// bigger sizes can leave the answers unknown. Returns `None` if there is no generator for the day.
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<Generated> {
+    let rng = &mut Rng::new(seed);
+    let size = size.max(1);
+
+    let generated = match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng),
+        7 => day07(rng, size),
+        8 => day08(rng, size),
+        9 => day09(rng, size),
+        10 => day10(rng, size),
+        11 => day11(rng, size),
+        12 => day12(rng, size),
+        13 => day13(rng, size),
+        14 => day14(rng, size),
+        15 => day15(rng, size),
+        16 => day16(rng, size),
+        17 => day17(rng, size),
+        18 => day18(rng, size),
+        19 => day19(rng, size),
+        20 => day20(rng, size),
+        21 => day21(rng, size),
+        22 => day22(rng, size),
+        23 => day23(rng, size),
+        24 => day24(rng, size),
+        25 => day25(rng, size),
+        _ => return None,
+    };
+
+    Some(generated)
+}
+
+fn letters(rng: &mut Rng, alphabet: &[u8], len: usize) -> String {
+    (0..len)
+        .map(|_| alphabet[rng.index(alphabet.len())] as char)
+        .collect()
+}
+
+// A name that was not used before.
+fn unique_name(rng: &mut Rng, used: &mut HashSet<String>, alphabet: &[u8], len: usize) -> String {
+    loop {
+        let name = letters(rng, alphabet, len);
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+fn join<T: ToString>(items: &[T], separator: &str) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+const DIGIT_NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// The digit at the start of `s`, also when it is spelled out if `spelled` is set.
+fn digit_at(s: &str, spelled: bool) -> Option<u64> {
+    let first = s.as_bytes()[0];
+    if first.is_ascii_digit() {
+        return Some((first - b'0') as u64);
+    }
+
+    let spelled = spelled.then(|| DIGIT_NAMES.iter().position(|name| s.starts_with(name)));
+    spelled.flatten().map(|i| i as u64 + 1)
+}
+
+fn calibration_value(line: &str, spelled: bool) -> u64 {
+    let digits = (0..line.len())
+        .filter_map(|i| digit_at(&line[i..], spelled))
+        .collect::<Vec<_>>();
+    digits[0] * 10 + digits[digits.len() - 1]
+}
+
+fn day01(rng: &mut Rng, size: usize) -> Generated {
+    let mut lines = Vec::new();
+
+    for _ in 0..size * 10 {
+        let mut line = String::new();
+
+        for _ in 0..rng.range(1..6) {
+            match rng.index(4) {
+                0 => line.push((b'1' + rng.index(9) as u8) as char),
+                1 => line += DIGIT_NAMES[rng.index(9)],
+                _ => {
+                    let len = rng.index(4) + 1;
+                    line += &letters(rng, LOWERCASE, len);
+                }
+            }
+        }
+
+        // Part 1 needs a digit on every line
+        if !line.bytes().any(|b| b.is_ascii_digit()) {
+            let at = rng.index(line.len() + 1);
+            line.insert(at, (b'1' + rng.index(9) as u8) as char);
+        }
+
+        lines.push(line);
+    }
+
+    let part1 = lines
+        .iter()
+        .map(|l| calibration_value(l, false))
+        .sum::<u64>();
+    let part2 = lines
+        .iter()
+        .map(|l| calibration_value(l, true))
+        .sum::<u64>();
+
+    Generated::new(lines.join("\n") + "\n", part1, part2)
+}
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+fn day02(rng: &mut Rng, size: usize) -> Generated {
+    let mut text = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for id in 1..=size * 10 {
+        let mut most = [0u64; 3];
+        let mut draws = Vec::new();
+
+        for _ in 0..rng.range(1..7) {
+            let mut colors = [0, 1, 2];
+            rng.shuffle(&mut colors);
+
+            let grabs = colors[..rng.index(3) + 1]
+                .iter()
+                .map(|&color| {
+                    let amount = rng.range(1..21) as u64;
+                    most[color] = most[color].max(amount);
+                    format!("{amount} {}", COLORS[color])
+                })
+                .collect::<Vec<_>>();
+
+            draws.push(grabs.join(", "));
+        }
+
+        if most[0] <= 12 && most[1] <= 13 && most[2] <= 14 {
+            part1 += id;
+        }
+        part2 += most.iter().product::<u64>();
+
+        text += &format!("Game {id}: {}\n", draws.join("; "));
+    }
+
+    Generated::new(text, part1, part2)
+}
+
+const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+
+fn day03(rng: &mut Rng, size: usize) -> Generated {
+    let side = 10 + size * 2;
+    let mut grid = vec![vec![b'.'; side]; side];
+    // Every number is different, since the solver tells the numbers around a gear apart by value.
+    let mut used = HashSet::new();
+    let mut numbers = Vec::new();
+
+    for (y, row) in grid.iter_mut().enumerate() {
+        let mut x = rng.index(4);
+
+        // Big grids run out of numbers and leave the rows at the bottom empty
+        while used.len() < 999 {
+            let value = loop {
+                let value = rng.range(1..1000) as u64;
+                if used.insert(value) {
+                    break value;
+                }
+            };
+
+            let len = value.to_string().len();
+            if x + len > side {
+                break;
+            }
+
+            row[x..x + len].copy_from_slice(value.to_string().as_bytes());
+            numbers.push((y, x..x + len, value));
+            x += len + 1 + rng.index(6);
+        }
+    }
+
+    for _ in 0..side * side / 8 {
+        let (y, x) = (rng.index(side), rng.index(side));
+        if grid[y][x] == b'.' {
+            grid[y][x] = if rng.one_in(3) {
+                b'*'
+            } else {
+                SYMBOLS[rng.index(SYMBOLS.len())]
+            };
+        }
+    }
+
+    let around = |y: usize, xs: std::ops::Range<usize>| {
+        let ys = y.saturating_sub(1)..(y + 2).min(side);
+        ys.flat_map(move |ny| {
+            (xs.start.saturating_sub(1)..(xs.end + 1).min(side)).map(move |nx| (ny, nx))
+        })
+    };
+
+    let is_symbol = |c: u8| c != b'.' && !c.is_ascii_digit();
+
+    let part1 = numbers
+        .iter()
+        .filter(|(y, xs, _)| around(*y, xs.clone()).any(|(ny, nx)| is_symbol(grid[ny][nx])))
+        .map(|(_, _, value)| value)
+        .sum::<u64>();
+
+    let mut gears = HashMap::<(usize, usize), Vec<u64>>::new();
+    for (y, xs, value) in &numbers {
+        for (ny, nx) in around(*y, xs.clone()) {
+            if grid[ny][nx] == b'*' {
+                gears.entry((ny, nx)).or_default().push(*value);
+            }
+        }
+    }
+
+    let part2 = gears
+        .values()
+        .filter(|values| values.len() == 2)
+        .map(|values| values[0] * values[1])
+        .sum::<u64>();
+
+    let text = grid
+        .iter()
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect();
+
+    Generated::new(text, part1, part2)
+}
+
+fn day04(rng: &mut Rng, size: usize) -> Generated {
+    let n = size * 10;
+    let mut text = String::new();
+    let mut matches = Vec::new();
+
+    for i in 0..n {
+        let mut numbers = (1..100).collect::<Vec<u64>>();
+        rng.shuffle(&mut numbers);
+
+        let (winners, others) = numbers.split_at(5);
+        // A card never wins copies of cards past the end of the table. Most cards win nothing, or
+        // the number of copies grows exponentially with the number of cards.
+        let most = 5.min(n - i - 1);
+        let count = if most > 0 && rng.one_in(4) {
+            rng.index(most) + 1
+        } else {
+            0
+        };
+
+        let mut draws = winners[..count].to_vec();
+        draws.extend(&others[..8 - count]);
+        rng.shuffle(&mut draws);
+
+        let numbers = |xs: &[u64]| {
+            join(
+                &xs.iter().map(|x| format!("{x:2}")).collect::<Vec<_>>(),
+                " ",
+            )
+        };
+        text += &format!(
+            "Card {:3}: {} | {}\n",
+            i + 1,
+            numbers(winners),
+            numbers(&draws)
+        );
+
+        matches.push(count);
+    }
+
+    let part1 = matches
+        .iter()
+        .map(|&count| if count == 0 { 0 } else { 1u64 << (count - 1) })
+        .sum::<u64>();
+
+    let mut copies = vec![1u64; n];
+    for i in 0..n {
+        for j in i + 1..=i + matches[i] {
+            copies[j] += copies[i];
+        }
+    }
+
+    Generated::new(text, part1, copies.iter().sum::<u64>())
+}
+
+const ALMANAC: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+fn day05(rng: &mut Rng, size: usize) -> Generated {
+    let domain = size as i64 * 1000;
+    let seeds = (0..8)
+        .map(|i| match i % 2 {
+            0 => rng.range(0..domain),
+            _ => rng.range(1..domain / 10),
+        })
+        .collect::<Vec<_>>();
+
+    let mut text = format!("seeds: {}\n", join(&seeds, " "));
+    // Every map as `(destination, source, length)`
+    let mut maps = Vec::new();
+
+    for name in ALMANAC {
+        let mut entries = Vec::new();
+        let mut start = 0;
+
+        while start < domain {
+            let len = rng.range(1..domain / 4);
+            if entries.is_empty() || !rng.one_in(3) {
+                entries.push((rng.range(0..domain * 2), start, len));
+            }
+            start += len;
+        }
+
+        rng.shuffle(&mut entries);
+        text += &format!("\n{name} map:\n");
+        for (dst, src, len) in &entries {
+            text += &format!("{dst} {src} {len}\n");
+        }
+
+        maps.push(entries);
+    }
+
+    let location = |seed: i64| {
+        maps.iter().fold(seed, |x, map| {
+            map.iter()
+                .find(|&&(_, src, len)| (src..src + len).contains(&x))
+                .map_or(x, |&(dst, src, _)| dst + x - src)
+        })
+    };
+
+    let part1 = seeds.iter().map(|&seed| location(seed)).min().unwrap();
+    let part2 = seeds
+        .chunks_exact(2)
+        .flat_map(|pair| pair[0]..pair[0] + pair[1])
+        .map(location)
+        .min()
+        .unwrap();
+
+    Generated::new(text, part1, part2)
+}
+
+fn ways_to_win(time: u64, distance: u64) -> u64 {
+    (0..=time)
+        .filter(|hold| hold * (time - hold) > distance)
+        .count() as u64
+}
+
+fn day06(rng: &mut Rng) -> Generated {
+    let times = (0..3).map(|_| rng.range(7..100) as u64).collect::<Vec<_>>();
+    let distances = times
+        .iter()
+        .map(|&t| rng.range(1..((t / 2) * (t - t / 2)) as i64) as u64)
+        .collect::<Vec<_>>();
+
+    let part1 = times
+        .iter()
+        .zip(&distances)
+        .map(|(&t, &d)| ways_to_win(t, d))
+        .product::<u64>();
+
+    // Part 2 reads the numbers with the spaces between them removed
+    let time = join(&times, "").parse().unwrap();
+    let distance = join(&distances, "").parse().unwrap();
+
+    let row = |xs: &[u64]| {
+        join(
+            &xs.iter().map(|x| format!("{x:4}")).collect::<Vec<_>>(),
+            " ",
+        )
+    };
+    let text = format!("Time:     {}\nDistance: {}\n", row(&times), row(&distances));
+
+    Generated::new(text, part1, ways_to_win(time, distance))
+}
+
+const CARDS: &[u8] = b"23456789TJQKA";
+const CARDS_WITH_JOKER: &[u8] = b"J23456789TQKA";
+
+// How strong the kind of hand is, from high card at 0 to five of a kind at 6.
+fn hand_kind(hand: &[u8]) -> u8 {
+    let mut counts = hand
+        .iter()
+        .map(|c| hand.iter().filter(|d| *d == c).count())
+        .collect::<Vec<_>>();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+
+    match (counts[0], counts[3]) {
+        (5, _) => 6,
+        (4, _) => 5,
+        (3, 2) => 4,
+        (3, _) => 3,
+        (2, 2) => 2,
+        (2, _) => 1,
+        _ => 0,
+    }
+}
+
+fn winnings(hands: &[(Vec<u8>, u64)], jokers: bool) -> u64 {
+    let order = if jokers { CARDS_WITH_JOKER } else { CARDS };
+
+    let mut ranked = hands
+        .iter()
+        .map(|(hand, bid)| {
+            // Every joker is tried as every other card
+            let wild = if jokers {
+                hand.iter().filter(|&&c| c == b'J').count()
+            } else {
+                0
+            };
+
+            let kind = (0..CARDS.len().pow(wild as u32))
+                .map(|mut choice| {
+                    let hand = hand
+                        .iter()
+                        .map(|&c| {
+                            if !jokers || c != b'J' {
+                                return c;
+                            }
+
+                            let card = CARDS[choice % CARDS.len()];
+                            choice /= CARDS.len();
+                            card
+                        })
+                        .collect::<Vec<_>>();
+                    hand_kind(&hand)
+                })
+                .max()
+                .unwrap();
+
+            let values = hand
+                .iter()
+                .map(|c| order.iter().position(|d| d == c).unwrap())
+                .collect::<Vec<_>>();
+
+            ((kind, values), *bid)
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort();
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank as u64 + 1) * bid)
+        .sum()
+}
+
+fn day07(rng: &mut Rng, size: usize) -> Generated {
+    let mut seen = HashSet::new();
+    let mut hands = Vec::new();
+
+    while hands.len() < size * 10 {
+        // Drawing from a few cards makes pairs and better hands more likely
+        let pool = (0..rng.range(1..6))
+            .map(|_| CARDS[rng.index(CARDS.len())])
+            .collect::<Vec<_>>();
+        let hand = (0..5)
+            .map(|_| pool[rng.index(pool.len())])
+            .collect::<Vec<_>>();
+
+        // Equal hands would make the order depend on the sort
+        if seen.insert(hand.clone()) {
+            hands.push((hand, rng.range(1..1001) as u64));
+        }
+    }
+
+    let text = hands
+        .iter()
+        .map(|(hand, bid)| format!("{} {bid}\n", String::from_utf8_lossy(hand)))
+        .collect();
+
+    Generated::new(text, winnings(&hands, false), winnings(&hands, true))
+}
+
+// Every ghost walks a loop that ends at its own `..Z` node after a number of steps that is a
+// multiple of the length of the instructions, which the solver of part 2 relies on.
+fn day08(rng: &mut Rng, size: usize) -> Generated {
+    let instructions = (0..rng.range(3..8))
+        .map(|_| if rng.one_in(2) { 'L' } else { 'R' })
+        .collect::<Vec<_>>();
+    let m = instructions.len();
+
+    let mut periods = [2, 3, 5, 7, 11, 13];
+    rng.shuffle(&mut periods);
+    let ghosts = rng.range(2..5) as usize;
+
+    let mut used = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut name = |rng: &mut Rng, last: u8| loop {
+        let name = format!("{}{}", letters(rng, UPPERCASE, 2), last as char);
+        if used.insert(name.clone()) {
+            break name;
+        }
+    };
+    let middle = |rng: &mut Rng| b'B' + rng.index(24) as u8;
+
+    // Every node as its name and the left and right node. The wrong way is filled in later.
+    let mut nodes: Vec<(String, Option<usize>, Option<usize>)> = Vec::new();
+    let mut starts = Vec::new();
+
+    for (ghost, period) in periods.iter().enumerate().take(ghosts) {
+        let steps = m * period;
+        let (start, exit) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (name(rng, b'A'), name(rng, b'Z')),
+        };
+
+        let first = nodes.len();
+        starts.push(first);
+        nodes.push((start, None, None));
+
+        // The start, then the loop of `steps` nodes, the last of which is the exit
+        for i in 1..=steps {
+            let label = if i == steps {
+                exit.clone()
+            } else {
+                let last = middle(rng);
+                name(rng, last)
+            };
+            nodes.push((label, None, None));
+
+            let from = if i == 1 { first } else { nodes.len() - 2 };
+            let to = nodes.len() - 1;
+            match instructions[(i - 1) % m] {
+                'L' => nodes[from].1 = Some(to),
+                _ => nodes[from].2 = Some(to),
+            }
+        }
+
+        // The exit goes around the loop again
+        let exit = nodes.len() - 1;
+        match instructions[steps % m] {
+            'L' => nodes[exit].1 = Some(first + 1),
+            _ => nodes[exit].2 = Some(first + 1),
+        }
+    }
+
+    for _ in 0..size * 5 {
+        let last = middle(rng);
+        let label = name(rng, last);
+        nodes.push((label, None, None));
+    }
+
+    let n = nodes.len();
+    let links = nodes
+        .iter()
+        .map(|(_, left, right)| {
+            (
+                left.unwrap_or_else(|| rng.index(n)),
+                right.unwrap_or_else(|| rng.index(n)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = nodes
+        .iter()
+        .zip(&links)
+        .map(|((label, _, _), &(left, right))| {
+            format!("{label} = ({}, {})", nodes[left].0, nodes[right].0)
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut lines);
+
+    let step = |at: usize, i: usize| match instructions[i % m] {
+        'L' => links[at].0,
+        _ => links[at].1,
+    };
+    let is_exit = |at: usize| nodes[at].0.ends_with('Z');
+
+    let mut at = starts[0];
+    let mut part1 = 0;
+    while nodes[at].0 != "ZZZ" {
+        at = step(at, part1);
+        part1 += 1;
+    }
+
+    let mut ghosts = starts;
+    let mut part2 = 0u64;
+    while !ghosts.iter().all(|&at| is_exit(at)) {
+        for at in ghosts.iter_mut() {
+            *at = step(*at, part2 as usize);
+        }
+        part2 += 1;
+    }
+
+    let text = format!(
+        "{}\n\n{}\n",
+        instructions.iter().collect::<String>(),
+        lines.join("\n")
+    );
+
+    Generated::new(text, part1, part2)
+}
+
+fn day09(rng: &mut Rng, size: usize) -> Generated {
+    let mut text = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for _ in 0..size * 10 {
+        // The history of a polynomial, which makes the differences end in zeroes
+        let coefficients = (0..rng.range(1..7))
+            .map(|_| rng.range(-5..6))
+            .collect::<Vec<_>>();
+        let value = |x: i64| coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+
+        let len = 21;
+        let history = (0..len).map(value).collect::<Vec<_>>();
+
+        text += &join(&history, " ");
+        text.push('\n');
+
+        part1 += value(len);
+        part2 += value(-1);
+    }
+
+    Generated::new(text, part1, part2)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Dir::Up => (-1, 0),
+            Dir::Right => (0, 1),
+            Dir::Down => (1, 0),
+            Dir::Left => (0, -1),
+        }
+    }
+
+    fn reverse(self) -> Dir {
+        Dir::ALL[(self as usize + 2) % 4]
+    }
+}
+
+// Moves `(y, x)` one step in `dir`, if that stays inside a `width` by `height` grid.
+fn step(
+    (y, x): (usize, usize),
+    dir: Dir,
+    (width, height): (usize, usize),
+) -> Option<(usize, usize)> {
+    let (dy, dx) = dir.offset();
+    let (y, x) = (y.checked_add_signed(dy)?, x.checked_add_signed(dx)?);
+    (y < height && x < width).then_some((y, x))
+}
+
+// Whether the blob has two cells that only touch at a corner in the 2 by 2 squares around `(y,
+// x)`.
+fn touches_diagonally(cells: &[bool], n: usize, (y, x): (usize, usize)) -> bool {
+    (y - 1..=y).any(|wy| {
+        (x - 1..=x).any(|wx| {
+            let [a, b, c, d] =
+                [(0, 0), (0, 1), (1, 0), (1, 1)].map(|(dy, dx)| cells[(wy + dy) * n + wx + dx]);
+            a == d && b == c && a != b
+        })
+    })
+}
+
+// Whether there are cells outside the blob that can not reach the edge of the grid.
+fn has_hole(cells: &[bool], n: usize) -> bool {
+    let mut seen = vec![false; n * n];
+    let mut queue = VecDeque::from([(0, 0)]);
+    seen[0] = true;
+
+    while let Some(p) = queue.pop_front() {
+        for dir in Dir::ALL {
+            if let Some((y, x)) = step(p, dir, (n, n)) {
+                if !cells[y * n + x] && !seen[y * n + x] {
+                    seen[y * n + x] = true;
+                    queue.push_back((y, x));
+                }
+            }
+        }
+    }
+
+    let outside = cells.iter().filter(|c| !**c).count();
+    seen.iter().filter(|s| **s).count() != outside
+}
+
+// A random blob of cells in an `n` by `n` grid, which does not reach the edge of the grid. It has
+// no holes and no cells that only touch at a corner, so its outline is a loop that does not touch
+// itself.
+fn blob(rng: &mut Rng, n: usize) -> Vec<bool> {
+    let mut cells = vec![false; n * n];
+    let mut members = vec![(n / 2, n / 2)];
+    cells[n / 2 * n + n / 2] = true;
+
+    let target = (n - 2) * (n - 2) / 2;
+
+    for _ in 0..target * 10 {
+        if members.len() >= target {
+            break;
+        }
+
+        let from = members[rng.index(members.len())];
+        let Some((y, x)) = step(from, Dir::ALL[rng.index(4)], (n, n)) else {
+            continue;
+        };
+
+        if y == 0 || x == 0 || y == n - 1 || x == n - 1 || cells[y * n + x] {
+            continue;
+        }
+
+        cells[y * n + x] = true;
+
+        if touches_diagonally(&cells, n, (y, x)) || has_hole(&cells, n) {
+            cells[y * n + x] = false;
+        } else {
+            members.push((y, x));
+        }
+    }
+
+    cells
+}
+
+// The outline of a blob, going clockwise around it one cell edge at a time. It starts at the top
+// left corner of the first cell of the blob, which is returned as well. Corners are numbered like
+// cells, with `(y, x)` the top left corner of cell `(y, x)`.
+fn outline(cells: &[bool], n: usize) -> ((usize, usize), Vec<Dir>) {
+    let cell = |y: usize, x: usize| {
+        (1..=n).contains(&y) && (1..=n).contains(&x) && cells[(y - 1) * n + x - 1]
+    };
+
+    // Whether the outline leaves the corner `(y, x)` in `dir`
+    let edge = |(y, x): (usize, usize), dir: Dir| {
+        // The cells around the corner, shifted by one so they can not underflow
+        let (nw, ne, sw, se) = (
+            cell(y, x),
+            cell(y, x + 1),
+            cell(y + 1, x),
+            cell(y + 1, x + 1),
+        );
+        match dir {
+            Dir::Up => nw != ne,
+            Dir::Down => sw != se,
+            Dir::Left => nw != sw,
+            Dir::Right => ne != se,
+        }
+    };
+
+    let first = cells
+        .iter()
+        .position(|&c| c)
+        .expect("a blob is never empty");
+    let start = (first / n, first % n);
+
+    let mut steps = vec![Dir::Right];
+    let mut at = (start.0, start.1 + 1);
+
+    while at != start {
+        let last = steps[steps.len() - 1];
+        let dir = Dir::ALL
+            .into_iter()
+            .find(|&dir| dir != last.reverse() && edge(at, dir))
+            .expect("the outline is a loop");
+
+        at = step(at, dir, (n + 1, n + 1)).unwrap();
+        steps.push(dir);
+    }
+
+    (start, steps)
+}
+
+// The pipe that connects the directions `a` and `b`.
+fn pipe(a: Dir, b: Dir) -> u8 {
+    use Dir::*;
+
+    let (a, b) = if (a as usize) < (b as usize) {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    match (a, b) {
+        (Up, Down) => b'|',
+        (Right, Left) => b'-',
+        (Up, Right) => b'L',
+        (Up, Left) => b'J',
+        (Down, Left) => b'7',
+        (Right, Down) => b'F',
+        _ => unreachable!("a pipe connects two different directions"),
+    }
+}
+
+// The loop goes around a blob of cells, where every cell is 2 by 2 tiles. The tiles that are not
+// part of the loop get random pipes.
+fn day10(rng: &mut Rng, size: usize) -> Generated {
+    let n = size + 2;
+    let cells = blob(rng, n);
+    let (start, steps) = outline(&cells, n);
+
+    let side = 2 * n + 1;
+    let mut tiles = (0..side * side)
+        .map(|_| b"|-LJ7F...."[rng.index(10)])
+        .collect::<Vec<_>>();
+    let mut on_loop = vec![false; side * side];
+
+    let mut at = (2 * start.0, 2 * start.1);
+    let mut last = steps[steps.len() - 1];
+
+    for &dir in &steps {
+        tiles[at.0 * side + at.1] = pipe(last.reverse(), dir);
+        on_loop[at.0 * side + at.1] = true;
+
+        at = step(at, dir, (side, side)).unwrap();
+        tiles[at.0 * side + at.1] = if matches!(dir, Dir::Up | Dir::Down) {
+            b'|'
+        } else {
+            b'-'
+        };
+        on_loop[at.0 * side + at.1] = true;
+
+        at = step(at, dir, (side, side)).unwrap();
+        last = dir;
+    }
+
+    // Every step of the outline is two tiles of the loop
+    let s = (0..side * side)
+        .filter(|&i| on_loop[i])
+        .nth(rng.index(steps.len() * 2))
+        .unwrap();
+    tiles[s] = b'S';
+
+    // Pipes next to the start that are not part of the loop should not connect to it.
+    for dir in Dir::ALL {
+        if let Some((y, x)) = step((s / side, s % side), dir, (side, side)) {
+            if !on_loop[y * side + x] {
+                tiles[y * side + x] = b'.';
+            }
+        }
+    }
+
+    // Inside are the middles of the cells, the edges between two cells and the corners between
+    // four cells of the blob.
+    let cell = |y: usize, x: usize| y < n && x < n && cells[y * n + x];
+    let inside = (0..side)
+        .flat_map(|y| (0..side).map(move |x| (y, x)))
+        .filter(|&(y, x)| {
+            let (cy, cx) = (y / 2, x / 2);
+            match (y % 2, x % 2) {
+                (1, 1) => cell(cy, cx),
+                (0, 1) => cy > 0 && cell(cy - 1, cx) && cell(cy, cx),
+                (1, 0) => cx > 0 && cell(cy, cx - 1) && cell(cy, cx),
+                _ => {
+                    cy > 0
+                        && cx > 0
+                        && cell(cy - 1, cx - 1)
+                        && cell(cy - 1, cx)
+                        && cell(cy, cx - 1)
+                        && cell(cy, cx)
+                }
+            }
+        })
+        .count();
+
+    let text = tiles
+        .chunks(side)
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect();
+
+    Generated::new(text, steps.len(), inside)
+}
+
+fn day11(rng: &mut Rng, size: usize) -> Generated {
+    let side = size * 3 + 5;
+    let mut grid = vec![vec![b'.'; side]; side];
+
+    for _ in 0..size * 3 {
+        grid[rng.index(side)][rng.index(side)] = b'#';
+    }
+
+    let empty_rows = (0..side)
+        .filter(|&y| grid[y].iter().all(|&c| c == b'.'))
+        .collect::<Vec<_>>();
+    let empty_cols = (0..side)
+        .filter(|&x| grid.iter().all(|row| row[x] == b'.'))
+        .collect::<Vec<_>>();
+
+    let distances = |factor: u64| {
+        let galaxies = (0..side)
+            .flat_map(|y| (0..side).map(move |x| (y, x)))
+            .filter(|&(y, x)| grid[y][x] == b'#')
+            .map(|(y, x)| {
+                let grow = |i: usize, empty: &[usize]| {
+                    i as u64 + (factor - 1) * empty.iter().filter(|&&e| e < i).count() as u64
+                };
+                (grow(y, &empty_rows), grow(x, &empty_cols))
+            })
+            .collect::<Vec<_>>();
+
+        let mut sum = 0;
+        for (i, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[i + 1..] {
+                sum += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+            }
+        }
+        sum
+    };
+
+    let text = grid
+        .iter()
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect();
+
+    Generated::new(text, distances(2), distances(1_000_000))
+}
+
+// The sizes of the groups of damaged springs.
+fn spring_groups(springs: &[u8]) -> Vec<usize> {
+    springs
+        .split(|&c| c == b'.')
+        .filter(|run| !run.is_empty())
+        .map(<[u8]>::len)
+        .collect()
+}
+
+// Part 2 unfolds the records to five times their size, which is too much to try every way for.
+fn day12(rng: &mut Rng, size: usize) -> Generated {
+    let mut text = String::new();
+    let mut part1 = 0;
+
+    for _ in 0..size * 10 {
+        let len = rng.index(13) + 4;
+        let mut springs = (0..len)
+            .map(|_| if rng.one_in(2) { b'#' } else { b'.' })
+            .collect::<Vec<_>>();
+        if !springs.contains(&b'#') {
+            springs[rng.index(len)] = b'#';
+        }
+
+        let groups = spring_groups(&springs);
+
+        let mut record = springs.clone();
+        let mut unknown = Vec::new();
+        for (i, c) in record.iter_mut().enumerate() {
+            if unknown.len() < 12 && rng.one_in(2) {
+                *c = b'?';
+                unknown.push(i);
+            }
+        }
+
+        for choice in 0..1u32 << unknown.len() {
+            let mut springs = record.clone();
+            for (bit, &i) in unknown.iter().enumerate() {
+                springs[i] = if choice >> bit & 1 == 1 { b'#' } else { b'.' };
+            }
+
+            if spring_groups(&springs) == groups {
+                part1 += 1u64;
+            }
+        }
+
+        text += &format!(
+            "{} {}\n",
+            String::from_utf8_lossy(&record),
+            join(&groups, ",")
+        );
+    }
+
+    Generated {
+        text,
+        answers: [Some(part1.into()), None],
+    }
+}
+
+// Every line the pattern reflects in, as columns to the left or 100 times the rows above it.
+fn reflections(grid: &[Vec<bool>]) -> Vec<usize> {
+    let (h, w) = (grid.len(), grid[0].len());
+    let mut found = Vec::new();
+
+    for c in 1..w {
+        let mirrored = |row: &Vec<bool>| (0..c.min(w - c)).all(|k| row[c - 1 - k] == row[c + k]);
+        if grid.iter().all(mirrored) {
+            found.push(c);
+        }
+    }
+
+    for r in 1..h {
+        if (0..r.min(h - r)).all(|k| grid[r - 1 - k] == grid[r + k]) {
+            found.push(100 * r);
+        }
+    }
+
+    found
+}
+
+// The one reflection of the pattern, and the other one after fixing the smudge. `None` if there is
+// not exactly one of either.
+fn summarize(grid: &mut [Vec<bool>]) -> Option<(usize, usize)> {
+    let &[line] = reflections(grid).as_slice() else {
+        return None;
+    };
+
+    let mut fixed = HashSet::new();
+
+    for y in 0..grid.len() {
+        for x in 0..grid[0].len() {
+            grid[y][x] = !grid[y][x];
+            fixed.extend(reflections(grid).into_iter().filter(|&l| l != line));
+            grid[y][x] = !grid[y][x];
+        }
+    }
+
+    let fixed = fixed.into_iter().collect::<Vec<_>>();
+    match fixed.as_slice() {
+        &[smudged] => Some((line, smudged)),
+        _ => None,
+    }
+}
+
+// Every pattern is made to reflect in a column and, but for a single tile, in a row. Patterns that
+// accidentally reflect in other places too are thrown away.
+fn day13(rng: &mut Rng, size: usize) -> Generated {
+    let mut patterns = Vec::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    while patterns.len() < size {
+        let (w, h) = (rng.index(11) + 5, rng.index(11) + 5);
+        let mut grid = (0..h)
+            .map(|_| (0..w).map(|_| rng.one_in(2)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // Mirror the columns left of `k` and leave the columns from `2 * k` alone
+        let k = rng.index((w - 1) / 2) + 1;
+        for row in grid.iter_mut() {
+            for j in 0..k {
+                row[2 * k - 1 - j] = row[j];
+            }
+        }
+
+        let r = rng.index(h - 1) + 1;
+        let reach = r.min(h - r);
+        for i in r..r + reach {
+            grid[i] = grid[2 * r - 1 - i].clone();
+        }
+
+        let (y, x) = (
+            r - reach + rng.index(2 * reach),
+            2 * k + rng.index(w - 2 * k),
+        );
+        grid[y][x] = !grid[y][x];
+
+        if rng.one_in(2) {
+            grid = (0..w)
+                .map(|x| (0..h).map(|y| grid[y][x]).collect())
+                .collect();
+        }
+
+        let Some((line, smudged)) = summarize(&mut grid) else {
+            continue;
+        };
+
+        part1 += line;
+        part2 += smudged;
+
+        let pattern = grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&c| if c { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        patterns.push(pattern.join("\n"));
+    }
+
+    Generated::new(patterns.join("\n\n") + "\n", part1, part2)
+}
+
+// Moves the round rocks one tile at a time, until none of them can move anymore.
+fn roll(grid: &mut [Vec<u8>], dir: Dir) {
+    let size = (grid[0].len(), grid.len());
+    let mut moved = true;
+
+    while moved {
+        moved = false;
+
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                if grid[y][x] != b'O' {
+                    continue;
+                }
+
+                if let Some((ny, nx)) = step((y, x), dir, size) {
+                    if grid[ny][nx] == b'.' {
+                        grid[ny][nx] = b'O';
+                        grid[y][x] = b'.';
+                        moved = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn north_load(grid: &[Vec<u8>]) -> usize {
+    grid.iter()
+        .enumerate()
+        .map(|(y, row)| row.iter().filter(|&&c| c == b'O').count() * (grid.len() - y))
+        .sum()
+}
+
+fn day14(rng: &mut Rng, size: usize) -> Generated {
+    let side = size + 5;
+    let grid = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| match rng.index(8) {
+                    0 | 1 => b'O',
+                    2 => b'#',
+                    _ => b'.',
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut tilted = grid.clone();
+    roll(&mut tilted, Dir::Up);
+    let part1 = north_load(&tilted);
+
+    // Spin until the platform repeats and skip ahead by whole periods
+    let mut history = vec![grid.clone()];
+    let mut seen = HashMap::from([(grid.clone(), 0)]);
+    let mut platform = grid.clone();
+
+    let (start, period) = loop {
+        for dir in [Dir::Up, Dir::Left, Dir::Down, Dir::Right] {
+            roll(&mut platform, dir);
+        }
+
+        if let Some(&start) = seen.get(&platform) {
+            break (start, history.len() - start);
+        }
+
+        seen.insert(platform.clone(), history.len());
+        history.push(platform.clone());
+    };
+
+    let spins = 1_000_000_000;
+    let part2 = north_load(&history[start + (spins - start) % period]);
+
+    let text = grid
+        .iter()
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect();
+
+    Generated::new(text, part1, part2)
+}
+
+fn hash(s: &str) -> usize {
+    s.bytes().fold(0, |acc, b| (acc + b as usize) * 17 % 256)
+}
+
+fn day15(rng: &mut Rng, size: usize) -> Generated {
+    let labels = (0..size * 3)
+        .map(|_| {
+            let len = rng.index(5) + 2;
+            letters(rng, LOWERCASE, len)
+        })
+        .collect::<Vec<_>>();
+
+    let mut boxes = vec![Vec::<(String, usize)>::new(); 256];
+    let mut steps = Vec::new();
+
+    for _ in 0..size * 20 {
+        let label = &labels[rng.index(labels.len())];
+        let lenses = &mut boxes[hash(label)];
+        let at = lenses.iter().position(|(l, _)| l == label);
+
+        if rng.one_in(3) {
+            steps.push(format!("{label}-"));
+            if let Some(at) = at {
+                lenses.remove(at);
+            }
+        } else {
+            let focal = rng.index(9) + 1;
+            steps.push(format!("{label}={focal}"));
+            match at {
+                Some(at) => lenses[at].1 = focal,
+                None => lenses.push((label.clone(), focal)),
+            }
+        }
+    }
+
+    let part1 = steps.iter().map(|s| hash(s)).sum::<usize>();
+    let part2 = boxes
+        .iter()
+        .enumerate()
+        .flat_map(|(i, lenses)| {
+            lenses
+                .iter()
+                .enumerate()
+                .map(move |(j, (_, focal))| (i + 1) * (j + 1) * focal)
+        })
+        .sum::<usize>();
+
+    Generated::new(steps.join(",") + "\n", part1, part2)
+}
+
+// Follows the beam that enters `start` going `dir` and counts the tiles it passes.
+fn energized(grid: &[Vec<u8>], start: (usize, usize), dir: Dir) -> usize {
+    let size = (grid[0].len(), grid.len());
+    let mut seen = HashSet::from([(start, dir)]);
+    let mut queue = VecDeque::from([(start, dir)]);
+
+    while let Some(((y, x), dir)) = queue.pop_front() {
+        let out = match (grid[y][x], dir) {
+            (b'/', _) => vec![Dir::ALL[[1, 0, 3, 2][dir as usize]]],
+            (b'\\', _) => vec![Dir::ALL[[3, 2, 1, 0][dir as usize]]],
+            (b'|', Dir::Left | Dir::Right) => vec![Dir::Up, Dir::Down],
+            (b'-', Dir::Up | Dir::Down) => vec![Dir::Left, Dir::Right],
+            _ => vec![dir],
+        };
+
+        for dir in out {
+            if let Some(next) = step((y, x), dir, size) {
+                if seen.insert((next, dir)) {
+                    queue.push_back((next, dir));
+                }
+            }
+        }
+    }
+
+    seen.iter().map(|(p, _)| p).collect::<HashSet<_>>().len()
+}
+
+fn day16(rng: &mut Rng, size: usize) -> Generated {
+    let side = size + 5;
+    let grid = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| match rng.index(20) {
+                    0 => b'/',
+                    1 => b'\\',
+                    2 => b'|',
+                    3 => b'-',
+                    _ => b'.',
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let part1 = energized(&grid, (0, 0), Dir::Right);
+
+    let last = side - 1;
+    let part2 = (0..side)
+        .flat_map(|i| {
+            [
+                ((0, i), Dir::Down),
+                ((last, i), Dir::Up),
+                ((i, 0), Dir::Right),
+                ((i, last), Dir::Left),
+            ]
+        })
+        .map(|(start, dir)| energized(&grid, start, dir))
+        .max()
+        .unwrap();
+
+    let text = grid
+        .iter()
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect();
+
+    Generated::new(text, part1, part2)
+}
+
+// The least heat lost going from the top left to the bottom right, moving at least `min` and at
+// most `max` tiles in a line.
+fn least_heat_loss(grid: &[Vec<u32>], min: usize, max: usize) -> u32 {
+    let size = (grid[0].len(), grid.len());
+    let goal = (size.1 - 1, size.0 - 1);
+
+    let mut best = HashMap::new();
+    // Heat loss, position, direction and how far it went in that direction. Having gone nowhere
+    // at the start allows turning either way.
+    let mut heap = BinaryHeap::from([
+        (std::cmp::Reverse(0), (0, 0), Dir::Right as usize, 0),
+        (std::cmp::Reverse(0), (0, 0), Dir::Down as usize, 0),
+    ]);
+
+    while let Some((std::cmp::Reverse(loss), at, dir, run)) = heap.pop() {
+        if at == goal && run >= min {
+            return loss;
+        }
+
+        if best.get(&(at, dir, run)).is_some_and(|&b| b < loss) {
+            continue;
+        }
+
+        for next in Dir::ALL {
+            let straight = next as usize == dir;
+            if next == Dir::ALL[dir].reverse()
+                || (straight && run == max)
+                || (!straight && run != 0 && run < min)
+            {
+                continue;
+            }
+
+            let Some((y, x)) = step(at, next, size) else {
+                continue;
+            };
+
+            let state = ((y, x), next as usize, if straight { run + 1 } else { 1 });
+            let loss = loss + grid[y][x];
+
+            if best.get(&state).is_none_or(|&b| loss < b) {
+                best.insert(state, loss);
+                heap.push((std::cmp::Reverse(loss), state.0, state.1, state.2));
+            }
+        }
+    }
+
+    unreachable!("the bottom right can always be reached")
+}
+
+fn day17(rng: &mut Rng, size: usize) -> Generated {
+    let side = size + 5;
+    let grid = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| rng.index(9) as u32 + 1)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let text = grid
+        .iter()
+        .map(|row| row.iter().map(|d| d.to_string()).collect::<String>() + "\n")
+        .collect();
+
+    Generated::new(
+        text,
+        least_heat_loss(&grid, 0, 3),
+        least_heat_loss(&grid, 4, 10),
+    )
+}
+
+// Digs out the trench of the plan and fills it, one cubic meter at a time.
+fn lagoon(plan: &[(Dir, usize)]) -> usize {
+    let mut at = (0isize, 0isize);
+    let mut trench = vec![at];
+
+    for &(dir, len) in plan {
+        let (dy, dx) = dir.offset();
+        for _ in 0..len {
+            at = (at.0 + dy, at.1 + dx);
+            trench.push(at);
+        }
+    }
+
+    // Surround the trench by a ring of ground, so the outside is connected
+    let min_y = trench.iter().map(|p| p.0).min().unwrap() - 1;
+    let min_x = trench.iter().map(|p| p.1).min().unwrap() - 1;
+    let height = (trench.iter().map(|p| p.0).max().unwrap() - min_y + 2) as usize;
+    let width = (trench.iter().map(|p| p.1).max().unwrap() - min_x + 2) as usize;
+
+    let mut dug = vec![false; width * height];
+    for (y, x) in trench {
+        dug[(y - min_y) as usize * width + (x - min_x) as usize] = true;
+    }
+
+    let mut outside = vec![false; width * height];
+    let mut queue = VecDeque::from([(0, 0)]);
+    outside[0] = true;
+
+    while let Some(p) = queue.pop_front() {
+        for dir in Dir::ALL {
+            if let Some((y, x)) = step(p, dir, (width, height)) {
+                if !dug[y * width + x] && !outside[y * width + x] {
+                    outside[y * width + x] = true;
+                    queue.push_back((y, x));
+                }
+            }
+        }
+    }
+
+    outside.iter().filter(|o| !**o).count()
+}
+
+// Both plans go around the same blob of cells, where the cells have random widths and heights. The
+// color is the plan of part 2.
+fn day18(rng: &mut Rng, size: usize) -> Generated {
+    let n = size + 2;
+    let cells = blob(rng, n);
+    let (start, steps) = outline(&cells, n);
+
+    let plan = |rng: &mut Rng, longest: i64| {
+        let widths = (0..n)
+            .map(|_| rng.range(1..longest + 1) as usize)
+            .collect::<Vec<_>>();
+        let heights = (0..n)
+            .map(|_| rng.range(1..longest + 1) as usize)
+            .collect::<Vec<_>>();
+
+        let mut at = start;
+        let mut plan: Vec<(Dir, usize)> = Vec::new();
+
+        for &dir in &steps {
+            let len = match dir {
+                Dir::Right => widths[at.1],
+                Dir::Left => widths[at.1 - 1],
+                Dir::Down => heights[at.0],
+                Dir::Up => heights[at.0 - 1],
+            };
+            at = step(at, dir, (n + 1, n + 1)).unwrap();
+
+            match plan.last_mut() {
+                Some((last, total)) if *last == dir => *total += len,
+                _ => plan.push((dir, len)),
+            }
+        }
+
+        plan
+    };
+
+    let plan1 = plan(rng, 5);
+    let plan2 = plan(rng, 30);
+
+    let text = plan1
+        .iter()
+        .zip(&plan2)
+        .map(|(&(dir1, len1), &(dir2, len2))| {
+            let dir1 = ["U", "R", "D", "L"][dir1 as usize];
+            let dir2 = [3, 0, 1, 2][dir2 as usize];
+            format!("{dir1} {len1} (#{len2:05x}{dir2})\n")
+        })
+        .collect();
+
+    Generated::new(text, lagoon(&plan1), lagoon(&plan2))
+}
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+
+// The rules of a workflow as the category, whether it is `<`, the limit and where to send the
+// part. The last rule has no condition.
+type Workflow = Vec<(Option<(usize, bool, u64)>, String)>;
+
+// Adds a workflow for the parts with ratings in `ranges` and returns its name. Every condition
+// splits the ratings that reach it in two non-empty parts, like in the puzzle input.
+fn add_workflow(
+    rng: &mut Rng,
+    workflows: &mut HashMap<String, Workflow>,
+    names: &mut HashSet<String>,
+    name: String,
+    mut ranges: [(u64, u64); 4],
+    depth: usize,
+) -> String {
+    let mut target = |rng: &mut Rng, workflows: &mut HashMap<_, _>, ranges| {
+        if depth > 0 && !rng.one_in(3) {
+            let len = 2 + rng.index(2);
+            let name = unique_name(rng, names, LOWERCASE, len);
+            add_workflow(rng, workflows, names, name, ranges, depth - 1)
+        } else if rng.one_in(2) {
+            "A".to_string()
+        } else {
+            "R".to_string()
+        }
+    };
+
+    let mut rules = Vec::new();
+
+    for _ in 0..rng.range(1..4) {
+        let category = rng.index(4);
+        let (lo, hi) = ranges[category];
+        if hi - lo < 2 {
+            continue;
+        }
+
+        // Ratings below `split` go one way and the rest the other
+        let split = rng.range(lo as i64 + 1..hi as i64) as u64;
+        let less = rng.one_in(2);
+
+        let mut matched = ranges;
+        if less {
+            matched[category] = (lo, split);
+            ranges[category] = (split, hi);
+        } else {
+            matched[category] = (split, hi);
+            ranges[category] = (lo, split);
+        }
+
+        let limit = if less { split } else { split - 1 };
+        let to = target(rng, workflows, matched);
+        rules.push((Some((category, less, limit)), to));
+    }
+
+    let to = target(rng, workflows, ranges);
+    rules.push((None, to));
+
+    workflows.insert(name.clone(), rules);
+    name
+}
+
+fn accepts(workflows: &HashMap<String, Workflow>, part: [u64; 4]) -> bool {
+    let mut at = "in";
+
+    loop {
+        let (_, to) = workflows[at]
+            .iter()
+            .find(|(condition, _)| match *condition {
+                Some((category, true, limit)) => part[category] < limit,
+                Some((category, false, limit)) => part[category] > limit,
+                None => true,
+            })
+            .unwrap();
+
+        match to.as_str() {
+            "A" => return true,
+            "R" => return false,
+            to => at = to,
+        }
+    }
+}
+
+// Part 2 splits the ratings at every limit in the workflows and tries one part for every
+// combination of ranges, which all go the same way.
+fn day19(rng: &mut Rng, size: usize) -> Generated {
+    let mut workflows = HashMap::new();
+    let mut names = HashSet::from(["in".to_string()]);
+    add_workflow(
+        rng,
+        &mut workflows,
+        &mut names,
+        "in".to_string(),
+        [(1, 4001); 4],
+        2,
+    );
+
+    let parts = (0..size * 20)
+        .map(|_| [0; 4].map(|_: u64| rng.range(1..4001) as u64))
+        .collect::<Vec<_>>();
+
+    let part1 = parts
+        .iter()
+        .filter(|&&part| accepts(&workflows, part))
+        .map(|part| part.iter().sum::<u64>())
+        .sum::<u64>();
+
+    let mut splits = [0; 4].map(|_| vec![1, 4001]);
+    for (condition, _) in workflows.values().flatten() {
+        if let Some((category, less, limit)) = *condition {
+            splits[category].push(if less { limit } else { limit + 1 });
+        }
+    }
+
+    let ranges = splits.map(|mut s| {
+        s.sort_unstable();
+        s.dedup();
+        s.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>()
+    });
+
+    let mut part2 = 0;
+    for &x in &ranges[0] {
+        for &m in &ranges[1] {
+            for &a in &ranges[2] {
+                for &s in &ranges[3] {
+                    if accepts(&workflows, [x.0, m.0, a.0, s.0]) {
+                        part2 += (x.1 - x.0) * (m.1 - m.0) * (a.1 - a.0) * (s.1 - s.0);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut lines = workflows
+        .iter()
+        .map(|(name, rules)| {
+            let rules = rules
+                .iter()
+                .map(|(condition, to)| match condition {
+                    Some((category, less, limit)) => {
+                        let op = if *less { '<' } else { '>' };
+                        format!("{}{op}{limit}:{to}", CATEGORIES[*category])
+                    }
+                    None => to.clone(),
+                })
+                .collect::<Vec<_>>();
+            format!("{name}{{{}}}", rules.join(","))
+        })
+        .collect::<Vec<_>>();
+    lines.sort();
+    rng.shuffle(&mut lines);
+
+    let parts = parts
+        .iter()
+        .map(|p| format!("{{x={},m={},a={},s={}}}", p[0], p[1], p[2], p[3]))
+        .collect::<Vec<_>>();
+
+    let text = format!("{}\n\n{}\n", lines.join("\n"), parts.join("\n"));
+
+    Generated::new(text, part1, part2)
+}
+
+enum Module {
+    Broadcaster,
+    FlipFlop(bool),
+    // The last pulse from every input
+    Conjunction(HashMap<usize, bool>),
+}
+
+struct Circuit {
+    modules: Vec<(Module, Vec<usize>)>,
+    rx: usize,
+}
+
+impl Circuit {
+    // Pushes the button and returns the number of low and high pulses and whether `rx` got a low
+    // pulse.
+    fn push(&mut self) -> (u64, u64, bool) {
+        let mut pulses = VecDeque::from([(usize::MAX, 0, false)]);
+        let (mut lows, mut highs, mut rx_low) = (0, 0, false);
+
+        while let Some((from, to, high)) = pulses.pop_front() {
+            if high {
+                highs += 1;
+            } else {
+                lows += 1;
+            }
+
+            rx_low |= to == self.rx && !high;
+
+            let Some((module, outputs)) = self.modules.get_mut(to) else {
+                continue;
+            };
+
+            let out = match module {
+                Module::Broadcaster => high,
+                Module::FlipFlop(_) if high => continue,
+                Module::FlipFlop(on) => {
+                    *on = !*on;
+                    *on
+                }
+                Module::Conjunction(last) => {
+                    last.insert(from, high);
+                    !last.values().all(|&h| h)
+                }
+            };
+
+            pulses.extend(outputs.iter().map(|&next| (to, next, out)));
+        }
+
+        (lows, highs, rx_low)
+    }
+}
+
+// The circuit is built like the puzzle input: every output of the broadcaster counts button
+// presses with a chain of flip-flops. A conjunction resets the counter when it reaches a prime, and
+// `rx` gets a low pulse when every counter resets at the same time.
+fn day20(rng: &mut Rng, size: usize) -> Generated {
+    let mut primes = [11u64, 13, 17, 19, 23];
+    rng.shuffle(&mut primes);
+    let counters = (size / 3).clamp(2, 4);
+
+    let mut used = HashSet::from(["rx".to_string()]);
+    let mut name = |rng: &mut Rng| unique_name(rng, &mut used, LOWERCASE, 2);
+
+    // Names and kinds in the order of their index, `rx` gets the next one
+    let mut modules: Vec<(String, char, Vec<usize>)> =
+        vec![("broadcaster".to_string(), ' ', vec![])];
+    let last = modules.len();
+    let merge = (name(rng), '&', vec![]);
+    modules.push(merge);
+
+    for &prime in &primes[..counters] {
+        let bits = 64 - prime.leading_zeros() as usize;
+        let first = modules.len();
+
+        for _ in 0..bits {
+            modules.push((name(rng), '%', vec![]));
+        }
+
+        let reset = modules.len();
+        modules.push((name(rng), '&', vec![]));
+        let inverter = modules.len();
+        modules.push((name(rng), '&', vec![]));
+
+        modules[0].2.push(first);
+        modules[reset].2.push(inverter);
+        modules[inverter].2.push(last);
+
+        for bit in 0..bits {
+            let flip_flop = first + bit;
+            if bit + 1 < bits {
+                modules[flip_flop].2.push(flip_flop + 1);
+            }
+
+            if prime >> bit & 1 == 1 {
+                modules[flip_flop].2.push(reset);
+            }
+
+            if prime >> bit & 1 == 0 || bit == 0 {
+                modules[reset].2.push(flip_flop);
+            }
+        }
+    }
+
+    let rx = modules.len();
+    modules[last].2.push(rx);
+
+    for (_, _, outputs) in modules.iter_mut() {
+        rng.shuffle(outputs);
+    }
+
+    let circuit = || {
+        let mut inputs = vec![Vec::new(); modules.len() + 1];
+        for (i, (_, _, outputs)) in modules.iter().enumerate() {
+            for &o in outputs {
+                inputs[o].push(i);
+            }
+        }
+
+        let modules = modules
+            .iter()
+            .enumerate()
+            .map(|(i, (_, kind, outputs))| {
+                let module = match kind {
+                    '%' => Module::FlipFlop(false),
+                    '&' => Module::Conjunction(inputs[i].iter().map(|&i| (i, false)).collect()),
+                    _ => Module::Broadcaster,
+                };
+                (module, outputs.clone())
+            })
+            .collect();
+
+        Circuit { modules, rx }
+    };
+
+    let mut part1 = circuit();
+    let (lows, highs) = (0..1000).fold((0, 0), |(lows, highs), _| {
+        let (l, h, _) = part1.push();
+        (lows + l, highs + h)
+    });
+
+    let mut part2 = circuit();
+    let presses = (1..).find(|_| part2.push().2).unwrap() as u64;
+    debug_assert_eq!(
+        presses as i64,
+        primes[..counters]
+            .iter()
+            .fold(1, |acc, &p| lcm(acc, p as i64))
+    );
+
+    let name_of = |i: usize| if i == rx { "rx" } else { modules[i].0.as_str() };
+    let mut lines = modules
+        .iter()
+        .map(|(name, kind, outputs)| {
+            let outputs = outputs.iter().map(|&o| name_of(o)).collect::<Vec<_>>();
+            format!(
+                "{}{name} -> {}",
+                kind.to_string().trim(),
+                outputs.join(", ")
+            )
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut lines);
+
+    Generated::new(lines.join("\n") + "\n", lows * highs, presses)
+}
+
+// Part 2 walks so far that the garden repeats thousands of times, which is too far to walk.
+fn day21(rng: &mut Rng, size: usize) -> Generated {
+    let side = 2 * size + 1;
+    let mut grid = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| if rng.one_in(8) { b'#' } else { b'.' })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    grid[size][size] = b'S';
+
+    let mut at = HashSet::from([(size, size)]);
+    for _ in 0..64 {
+        at = at
+            .iter()
+            .flat_map(|&p| Dir::ALL.map(|dir| step(p, dir, (side, side))))
+            .flatten()
+            .filter(|&(y, x)| grid[y][x] != b'#')
+            .collect();
+    }
+
+    let text = grid
+        .iter()
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect();
+
+    Generated {
+        text,
+        answers: [Some(at.len().into()), None],
+    }
+}
+
+// A brick as its lowest and highest corner.
+type Brick = ([usize; 3], [usize; 3]);
+
+fn cubes(&(lo, hi): &Brick) -> impl Iterator<Item = [usize; 3]> {
+    (lo[0]..=hi[0]).flat_map(move |x| {
+        (lo[1]..=hi[1]).flat_map(move |y| (lo[2]..=hi[2]).map(move |z| [x, y, z]))
+    })
+}
+
+// Lets the bricks fall one step at a time until they all rest on the ground or another brick.
+// Returns how many of them fell.
+fn settle(bricks: &mut [Brick], occupied: &mut HashSet<[usize; 3]>) -> usize {
+    bricks.sort_by_key(|(lo, _)| lo[2]);
+    let mut fell = 0;
+
+    for brick in bricks.iter_mut() {
+        let mut moved = false;
+
+        loop {
+            let below = cubes(brick)
+                .map(|[x, y, z]| [x, y, z - 1])
+                .filter(|c| !cubes(brick).any(|d| d == *c))
+                .collect::<Vec<_>>();
+
+            if brick.0[2] == 1 || below.iter().any(|c| occupied.contains(c)) {
+                break;
+            }
+
+            for c in cubes(brick) {
+                occupied.remove(&c);
+            }
+            brick.0[2] -= 1;
+            brick.1[2] -= 1;
+            for c in cubes(brick) {
+                occupied.insert(c);
+            }
+
+            moved = true;
+        }
+
+        fell += moved as usize;
+    }
+
+    fell
+}
+
+fn day22(rng: &mut Rng, size: usize) -> Generated {
+    let mut bricks = Vec::new();
+    let mut occupied = HashSet::new();
+
+    while bricks.len() < size * 10 {
+        let lo = [rng.index(10), rng.index(10), rng.index(size * 20) + 1];
+        let mut hi = lo;
+        let axis = rng.index(3);
+        hi[axis] += rng.index(4);
+
+        if hi[0] > 9 || hi[1] > 9 {
+            continue;
+        }
+
+        let brick = (lo, hi);
+        if cubes(&brick).any(|c| occupied.contains(&c)) {
+            continue;
+        }
+
+        occupied.extend(cubes(&brick));
+        bricks.push(brick);
+    }
+
+    let text = bricks
+        .iter()
+        .map(|(lo, hi)| {
+            format!(
+                "{},{},{}~{},{},{}\n",
+                lo[0], lo[1], lo[2], hi[0], hi[1], hi[2]
+            )
+        })
+        .collect();
+
+    settle(&mut bricks, &mut occupied);
+
+    let mut part1 = 0;
+    let mut part2 = 0;
+
+    for i in 0..bricks.len() {
+        let mut rest = bricks.clone();
+        let removed = rest.remove(i);
+        let mut occupied = occupied.clone();
+        for c in cubes(&removed) {
+            occupied.remove(&c);
+        }
+
+        let fell = settle(&mut rest, &mut occupied);
+        part1 += (fell == 0) as usize;
+        part2 += fell;
+    }
+
+    Generated::new(text, part1, part2)
+}
+
+// The longest walk from the top left to the bottom right without stepping on a tile twice.
+fn longest_walk(grid: &[Vec<u8>], slippery: bool) -> usize {
+    let size = (grid[0].len(), grid.len());
+    let goal = (size.1 - 1, size.0 - 2);
+    let mut seen = vec![vec![false; size.0]; size.1];
+
+    fn walk(
+        grid: &[Vec<u8>],
+        seen: &mut [Vec<bool>],
+        at: (usize, usize),
+        goal: (usize, usize),
+        size: (usize, usize),
+        slippery: bool,
+    ) -> Option<usize> {
+        if at == goal {
+            return Some(0);
+        }
+
+        seen[at.0][at.1] = true;
+
+        let dirs = match grid[at.0][at.1] {
+            b'^' if slippery => vec![Dir::Up],
+            b'>' if slippery => vec![Dir::Right],
+            b'v' if slippery => vec![Dir::Down],
+            b'<' if slippery => vec![Dir::Left],
+            _ => Dir::ALL.to_vec(),
+        };
+
+        let mut longest = None;
+        for dir in dirs {
+            let Some((y, x)) = step(at, dir, size) else {
+                continue;
+            };
+
+            let against = match grid[y][x] {
+                b'^' => dir == Dir::Down,
+                b'>' => dir == Dir::Left,
+                b'v' => dir == Dir::Up,
+                b'<' => dir == Dir::Right,
+                _ => false,
+            };
+
+            if grid[y][x] == b'#' || seen[y][x] || (slippery && against) {
+                continue;
+            }
+
+            if let Some(len) = walk(grid, seen, (y, x), goal, size, slippery) {
+                longest = longest.max(Some(len + 1));
+            }
+        }
+
+        seen[at.0][at.1] = false;
+        longest
+    }
+
+    walk(grid, &mut seen, (0, 1), goal, size, slippery).expect("the maze has a way through")
+}
+
+// A maze with a few loops in it. Slopes between the cells of the maze point away from the start,
+// so part 1 can not walk back.
+fn day23(rng: &mut Rng, size: usize) -> Generated {
+    let n = size / 2 + 2;
+    let side = 2 * n + 1;
+    let mut grid = vec![vec![b'#'; side]; side];
+
+    // Carve out a maze with a random depth first search
+    let mut stack = vec![(0, 0)];
+    let mut visited = vec![vec![false; n]; n];
+    visited[0][0] = true;
+    grid[1][1] = b'.';
+
+    while let Some(&cell) = stack.last() {
+        let next = Dir::ALL
+            .into_iter()
+            .filter_map(|dir| Some((dir, step(cell, dir, (n, n))?)))
+            .filter(|&(_, (y, x))| !visited[y][x])
+            .collect::<Vec<_>>();
+
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (_, (y, x)) = next[rng.index(next.len())];
+        visited[y][x] = true;
+        grid[2 * y + 1][2 * x + 1] = b'.';
+        grid[cell.0 + y + 1][cell.1 + x + 1] = b'.';
+        stack.push((y, x));
+    }
+
+    for _ in 0..size / 2 {
+        let (y, x) = (rng.index(n), rng.index(n));
+        if let Some((ny, nx)) = step((y, x), Dir::ALL[rng.index(4)], (n, n)) {
+            grid[y + ny + 1][x + nx + 1] = b'.';
+        }
+    }
+
+    grid[0][1] = b'.';
+    grid[side - 1][side - 2] = b'.';
+
+    let mut distance = vec![vec![usize::MAX; side]; side];
+    let mut queue = VecDeque::from([(0, 1)]);
+    distance[0][1] = 0;
+
+    while let Some(at) = queue.pop_front() {
+        for dir in Dir::ALL {
+            if let Some((y, x)) = step(at, dir, (side, side)) {
+                if grid[y][x] != b'#' && distance[y][x] == usize::MAX {
+                    distance[y][x] = distance[at.0][at.1] + 1;
+                    queue.push_back((y, x));
+                }
+            }
+        }
+    }
+
+    // The tiles between two cells get a slope towards the one that is further away
+    for y in 1..side - 1 {
+        for x in 1..side - 1 {
+            if grid[y][x] != b'.' || (y % 2 == 1 && x % 2 == 1) {
+                continue;
+            }
+
+            let (dir, a, b) = if y % 2 == 0 {
+                (Dir::Down, distance[y - 1][x], distance[y + 1][x])
+            } else {
+                (Dir::Right, distance[y][x - 1], distance[y][x + 1])
+            };
+
+            let dir = match a.cmp(&b) {
+                std::cmp::Ordering::Less => dir,
+                std::cmp::Ordering::Greater => dir.reverse(),
+                std::cmp::Ordering::Equal => continue,
+            };
+
+            grid[y][x] = b"^>v<"[dir as usize];
+        }
+    }
+
+    let text = grid
+        .iter()
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect();
+
+    let part2 = (size <= 12).then(|| longest_walk(&grid, false).into());
+
+    Generated {
+        text,
+        answers: [Some(longest_walk(&grid, true).into()), part2],
+    }
+}
+
+// Whether the paths of two hailstones cross inside the test area, ignoring the z axis.
+fn paths_cross(a: &([i128; 3], [i128; 3]), b: &([i128; 3], [i128; 3])) -> bool {
+    let (lo, hi) = (200_000_000_000_000, 400_000_000_000_000);
+    let ([ax, ay, _], [avx, avy, _]) = *a;
+    let ([bx, by, _], [bvx, bvy, _]) = *b;
+
+    // Solve `a + av * t = b + bv * s` with Cramer's rule, keeping the determinant positive
+    let mut d = bvx * avy - avx * bvy;
+    let mut t = bvx * (by - ay) - bvy * (bx - ax);
+    let mut s = avx * (by - ay) - avy * (bx - ax);
+
+    if d < 0 {
+        (d, t, s) = (-d, -t, -s);
+    }
+
+    let (x, y) = (ax * d + avx * t, ay * d + avy * t);
+
+    t >= 0 && s >= 0 && (lo * d..=hi * d).contains(&x) && (lo * d..=hi * d).contains(&y)
+}
+
+// The hailstones are made to all be hit by a rock thrown from a known position, which is the answer
+// to part 2.
+fn day24(rng: &mut Rng, size: usize) -> Generated {
+    let rock = [0; 3].map(|_: i64| rng.range(200_000_000_000_000..400_000_000_000_000));
+    let rock_velocity = [0; 3].map(|_: i64| rng.range(-300..301));
+
+    let mut stones: Vec<([i128; 3], [i128; 3])> = Vec::new();
+    let mut text = String::new();
+
+    while stones.len() < size * 3 {
+        let velocity = [0; 3].map(|_: i64| {
+            let v = rng.range(-300..300);
+            if v >= 0 {
+                v + 1
+            } else {
+                v
+            }
+        });
+        let time = rng.range(10_000_000_000..500_000_000_000);
+
+        let v = velocity.map(|v| v as i128);
+        // Parallel paths never cross, or are the same path everywhere
+        if stones.iter().any(|(_, w)| v[0] * w[1] == v[1] * w[0]) {
+            continue;
+        }
+
+        let position = [0, 1, 2].map(|i| rock[i] + (rock_velocity[i] - velocity[i]) * time);
+        stones.push((position.map(|p| p as i128), v));
+
+        text += &format!(
+            "{}, {}, {} @ {}, {}, {}\n",
+            position[0], position[1], position[2], velocity[0], velocity[1], velocity[2]
+        );
+    }
+
+    let mut part1 = 0;
+    for (i, a) in stones.iter().enumerate() {
+        part1 += stones[i + 1..].iter().filter(|b| paths_cross(a, b)).count();
+    }
+
+    Generated::new(text, part1, rock.iter().sum::<i64>())
+}
+
+// Two groups of components, that are connected by three wires. Within a group every component is
+// wired to the two after it in a circle, so cutting a group takes at least four wires.
+fn day25(rng: &mut Rng, size: usize) -> Generated {
+    let sizes = [0; 2].map(|_| size * 5 + rng.index(size * 5));
+    let n = sizes[0] + sizes[1];
+
+    let mut used = HashSet::new();
+    let names = (0..n)
+        .map(|_| unique_name(rng, &mut used, LOWERCASE, 3))
+        .collect::<Vec<_>>();
+
+    // Ordered, so the same seed lists the wires the same way
+    let mut wires = BTreeSet::new();
+    let mut wire = |a: usize, b: usize| {
+        wires.insert((a.min(b), a.max(b)));
+    };
+
+    for (offset, len) in [(0, sizes[0]), (sizes[0], sizes[1])] {
+        for i in 0..len {
+            wire(offset + i, offset + (i + 1) % len);
+            wire(offset + i, offset + (i + 2) % len);
+            wire(offset + i, offset + rng.index(len));
+        }
+    }
+
+    let ends = [0, 1].map(|group| {
+        let (offset, len) = [(0, sizes[0]), (sizes[0], sizes[1])][group];
+        let mut ends = (offset..offset + len).collect::<Vec<_>>();
+        rng.shuffle(&mut ends);
+        ends
+    });
+
+    for (&a, &b) in ends[0].iter().zip(&ends[1]).take(3) {
+        wire(a, b);
+    }
+
+    // Every wire is listed once, on the line of one of its ends
+    let mut lines = vec![Vec::new(); n];
+    for &(a, b) in &wires {
+        if a == b {
+            continue;
+        }
+
+        let (from, to) = if rng.one_in(2) { (a, b) } else { (b, a) };
+        lines[from].push(names[to].as_str());
+    }
+
+    let mut lines = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, to)| !to.is_empty())
+        .map(|(from, to)| format!("{}: {}", names[from], to.join(" ")))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut lines);
+
+    Generated {
+        text: lines.join("\n") + "\n",
+        answers: [Some((sizes[0] * sizes[1]).into()), None],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::registry::{self, Days, Status};
+
+    #[test]
+    fn solvers_agree_with_the_generated_answers() {
+        for day in 1..=25 {
+            for seed in 0..3 {
+                let generated = generate(day, seed, 4).unwrap();
+
+                for solver in registry::select(Days::single(day), None) {
+                    let part = solver.part as usize;
+                    if solver.status == Status::Unimplemented {
+                        continue;
+                    }
+
+                    let Some(expected) = &generated.answers[part - 1] else {
+                        continue;
+                    };
+
+                    assert_eq!(
+                        (solver.run)(&generated.text).as_ref(),
+                        Ok(expected),
+                        "{} with seed {seed}",
+                        solver.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_give_the_same_input() {
+        let a = generate(10, 7, 5).unwrap();
+        let b = generate(10, 7, 5).unwrap();
+        assert_eq!(a.text, b.text);
+        assert_eq!(a.answers, b.answers);
+        assert_ne!(a.text, generate(10, 8, 5).unwrap().text);
+
+        assert!(generate(26, 0, 5).is_none());
+    }
+}
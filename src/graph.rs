@@ -0,0 +1,187 @@
+use std::collections::BinaryHeap;
+
+use ahash::AHashMap;
+
+// Undirected graph with weighted edges between the vertices `0..len`. Adding an edge that is
+// already there adds to its weight.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    adjacent: Vec<AHashMap<usize, u64>>,
+}
+
+// The result of `min_cut`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cut {
+    // Sum of the weights of the cut edges
+    pub weight: u64,
+    // Edges between the two sides, with the smaller vertex first
+    pub edges: Vec<(usize, usize)>,
+    // For every vertex whether it is on the first side of the cut
+    pub side: Vec<bool>,
+    // Number of vertices on the first and second side
+    pub sizes: (usize, usize),
+}
+
+impl Graph {
+    pub fn new(len: usize) -> Graph {
+        Graph {
+            adjacent: vec![AHashMap::new(); len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacent.is_empty()
+    }
+
+    // Adds a vertex without edges and returns it.
+    pub fn add_vertex(&mut self) -> usize {
+        self.adjacent.push(AHashMap::new());
+        self.adjacent.len() - 1
+    }
+
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: u64) {
+        assert!(a != b, "an edge from vertex {a} to itself");
+        *self.adjacent[a].entry(b).or_default() += weight;
+        *self.adjacent[b].entry(a).or_default() += weight;
+    }
+
+    pub fn neighbors(&self, v: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.adjacent[v].iter().map(|(&u, &w)| (u, w))
+    }
+
+    // The cheapest way to split the vertices into two non-empty sides, where the cost is the
+    // weight of the edges between them. A graph that is not connected has a cut of weight 0.
+    // Returns `None` if there are fewer than two vertices.
+    //
+    // This is the Stoer-Wagner algorithm. Every phase orders the vertices by how strongly they are
+    // connected to the ones before them. The last vertex with everything else is a cut, after
+    // which the last two vertices are merged. The lightest of these cuts is a minimum cut.
+    pub fn min_cut(&self) -> Option<Cut> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+
+        let mut adjacent = self.adjacent.clone();
+        // The original vertices that were merged into each vertex
+        let mut members = (0..n).map(|v| vec![v]).collect::<Vec<_>>();
+        let mut active = (0..n).collect::<Vec<_>>();
+
+        let mut best = (u64::MAX, Vec::new());
+
+        let mut key = vec![0; n];
+        let mut added = vec![false; n];
+
+        while active.len() > 1 {
+            let mut heap = BinaryHeap::new();
+
+            for &v in &active {
+                key[v] = 0;
+                added[v] = false;
+                heap.push((0, v));
+            }
+
+            let mut order = Vec::with_capacity(active.len());
+
+            while let Some((k, v)) = heap.pop() {
+                if added[v] || k != key[v] {
+                    continue;
+                }
+
+                added[v] = true;
+                order.push(v);
+
+                for (&u, &w) in &adjacent[v] {
+                    if !added[u] {
+                        key[u] += w;
+                        heap.push((key[u], u));
+                    }
+                }
+            }
+
+            let [.., s, t] = order[..] else {
+                unreachable!("a phase orders at least two vertices")
+            };
+
+            if key[t] < best.0 {
+                best = (key[t], members[t].clone());
+            }
+
+            // Merge `t` into `s`
+            let merged = std::mem::take(&mut members[t]);
+            members[s].extend(merged);
+
+            for (u, w) in std::mem::take(&mut adjacent[t]) {
+                adjacent[u].remove(&t);
+
+                if u != s {
+                    *adjacent[s].entry(u).or_default() += w;
+                    *adjacent[u].entry(s).or_default() += w;
+                }
+            }
+
+            active.retain(|&v| v != t);
+        }
+
+        let (weight, first) = best;
+
+        let mut side = vec![false; n];
+        for &v in &first {
+            side[v] = true;
+        }
+
+        let mut edges = (0..n)
+            .flat_map(|a| self.neighbors(a).map(move |(b, _)| (a, b)))
+            .filter(|&(a, b)| a < b && side[a] != side[b])
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+
+        Some(Cut {
+            weight,
+            edges,
+            side,
+            sizes: (first.len(), n - first.len()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+
+    #[test]
+    fn min_cut_of_small_graphs() {
+        // Two triangles with a single edge between them
+        let mut g = Graph::new(6);
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+            g.add_edge(a, b, 1);
+        }
+
+        let cut = g.min_cut().unwrap();
+        assert_eq!(cut.weight, 1);
+        assert_eq!(cut.edges, vec![(2, 3)]);
+        assert_eq!(cut.sizes, (3, 3));
+        assert_ne!(cut.side[0], cut.side[5]);
+
+        // Heavy edges are not cut, even if there are fewer of them
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1, 5);
+        g.add_edge(1, 2, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 2, 1);
+        assert_eq!(g.min_cut().unwrap().weight, 3);
+
+        // A vertex without edges is cut off for free
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1, 1);
+        let cut = g.min_cut().unwrap();
+        assert_eq!((cut.weight, cut.edges.len()), (0, 0));
+        assert_eq!(cut.sizes.0.min(cut.sizes.1), 1);
+
+        assert_eq!(Graph::new(1).min_cut(), None);
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+// Requests that take longer than this are given up on.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl Method {
+    fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+        }
+    }
+}
+
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    // Sent as `application/x-www-form-urlencoded`
+    pub form: Option<String>,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub enum HttpError {
+    InvalidUrl(String),
+    Io(io::Error),
+    // The backend could not be started or did not give a valid response
+    Backend(String),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::InvalidUrl(url) => write!(f, "`{url}` is not a supported url"),
+            HttpError::Io(e) => write!(f, "request failed: {e}"),
+            HttpError::Backend(message) => write!(f, "request failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HttpError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for HttpError {
+    fn from(e: io::Error) -> HttpError {
+        HttpError::Io(e)
+    }
+}
+
+// Something that can send HTTP requests. Everything that talks to the outside world goes through
+// this, so that tests can use a local server instead.
+pub trait Http {
+    fn send(&self, request: &Request) -> Result<Response, HttpError>;
+}
+
+// The backend for `url`: plain `http://` urls are handled directly, everything else by curl.
+pub fn backend(url: &str) -> Box<dyn Http> {
+    if url.starts_with("http://") {
+        Box::new(PlainHttp)
+    } else {
+        Box::new(Curl)
+    }
+}
+
+// Runs the `curl` program, which does TLS for us.
+pub struct Curl;
+
+impl Http for Curl {
+    fn send(&self, request: &Request) -> Result<Response, HttpError> {
+        let mut cmd = Command::new("curl");
+        cmd.args(["--silent", "--show-error", "--max-time"])
+            .arg(TIMEOUT.as_secs().to_string())
+            .args(["--request", request.method.as_str()])
+            // The status code goes on a line of its own after the body
+            .args(["--write-out", "\n%{http_code}"]);
+
+        // The headers hold the session token, so they go to curl on stdin with the form instead of
+        // as arguments, where anyone on the machine could see them with `ps`.
+        let mut child = cmd
+            .args(["--config", "-"])
+            .arg(&request.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| HttpError::Backend(format!("could not run curl: {e}")))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(curl_config(request).as_bytes())?;
+        }
+
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(HttpError::Backend(stderr.trim().to_string()));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some((body, status)) = stdout.rsplit_once('\n') else {
+            return Err(HttpError::Backend("curl gave no status code".to_string()));
+        };
+
+        let status = status
+            .parse()
+            .map_err(|_| HttpError::Backend(format!("`{status}` is not a status code")))?;
+
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+// A curl config file with the headers and the form of `request`.
+fn curl_config(request: &Request) -> String {
+    let quote = |value: &str| {
+        let mut quoted = String::from("\"");
+        for c in value.chars() {
+            match c {
+                '"' => quoted += "\\\"",
+                '\\' => quoted += "\\\\",
+                '\n' => quoted += "\\n",
+                '\r' => quoted += "\\r",
+                '\t' => quoted += "\\t",
+                c => quoted.push(c),
+            }
+        }
+        quoted + "\""
+    };
+
+    let mut config = String::new();
+
+    for (name, value) in &request.headers {
+        config += &format!("header = {}\n", quote(&format!("{name}: {value}")));
+    }
+
+    // `data-raw` rather than `data-binary`, which would read a file if the form started with `@`
+    if let Some(form) = &request.form {
+        config += &format!("data-raw = {}\n", quote(form));
+    }
+
+    config
+}
+
+// HTTP/1.1 without TLS over a plain TCP connection, for local servers.
+pub struct PlainHttp;
+
+impl Http for PlainHttp {
+    fn send(&self, request: &Request) -> Result<Response, HttpError> {
+        let invalid = || HttpError::InvalidUrl(request.url.clone());
+
+        let rest = request.url.strip_prefix("http://").ok_or_else(invalid)?;
+        let (host, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, "/"),
+        };
+
+        if host.is_empty() {
+            return Err(invalid());
+        }
+
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{host}:80")
+        };
+
+        let mut stream = TcpStream::connect(address)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        let mut head = format!(
+            "{} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n",
+            request.method.as_str()
+        );
+
+        for (name, value) in &request.headers {
+            head += &format!("{name}: {value}\r\n");
+        }
+
+        let form = request.form.as_deref().unwrap_or("");
+        if request.form.is_some() {
+            head += "Content-Type: application/x-www-form-urlencoded\r\n";
+            head += &format!("Content-Length: {}\r\n", form.len());
+        }
+
+        head += "\r\n";
+
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(form.as_bytes())?;
+
+        // The server closes the connection after the response, so it ends where the stream does.
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+
+        parse_response(&String::from_utf8_lossy(&response))
+    }
+}
+
+fn parse_response(response: &str) -> Result<Response, HttpError> {
+    let invalid = |message: &str| HttpError::Backend(message.to_string());
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| invalid("the response has no end of headers"))?;
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| invalid("the response has no valid status line"))?;
+
+    let is_chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding") && value.trim() == "chunked"
+        })
+    });
+
+    if is_chunked {
+        return Err(invalid("chunked responses are not supported"));
+    }
+
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{curl_config, parse_response, Method, Request};
+
+    #[test]
+    fn parse_plain_response() {
+        let response =
+            parse_response("HTTP/1.1 404 Not Found\r\nContent-Length: 5\r\n\r\nnope\n").unwrap();
+
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "nope\n");
+
+        assert!(parse_response("HTTP/1.1 200 OK\r\n").is_err());
+        assert!(parse_response("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n").is_err());
+    }
+
+    #[test]
+    fn curl_config_quotes_values() {
+        let request = Request {
+            method: Method::Post,
+            url: "https://example.com".to_string(),
+            headers: vec![("Cookie".to_string(), "session=a\"b\\c".to_string())],
+            form: Some("level=1&answer=@x".to_string()),
+        };
+
+        assert_eq!(
+            curl_config(&request),
+            "header = \"Cookie: session=a\\\"b\\\\c\"\ndata-raw = \"level=1&answer=@x\"\n"
+        );
+    }
+}
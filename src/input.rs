@@ -0,0 +1,245 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_DIR: &str = "input";
+
+// Id of the inputs in the input directory, see `Loader::id`.
+pub const DEFAULT_ID: &str = "default";
+
+// Directory inside the input directory with the examples from the puzzle texts.
+pub const EXAMPLES_DIR: &str = "examples";
+
+// Profile with the scaled up inputs that `scale` writes, which are too big to run by default.
+pub const BIGBOY: &str = "bigboy";
+
+// Passing this as the input path reads the puzzle input from stdin instead.
+pub const STDIN: &str = "-";
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound { day: u8, path: PathBuf },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { day, path } => {
+                write!(
+                    f,
+                    "no input for day {day}: {} does not exist or is empty",
+                    path.display()
+                )
+            }
+            InputError::Io { path, source } => {
+                write!(f, "could not read {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::NotFound { .. } => None,
+            InputError::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+pub fn file_name(day: u8) -> String {
+    format!("day_{day:02}.txt")
+}
+
+// Examples are numbered per day, in the order they appear in the puzzle text.
+pub fn example_file_name(day: u8, example: u8) -> String {
+    format!("day_{day:02}_{example}.txt")
+}
+
+// Which inputs to read.
+pub enum Source {
+    // `dir/day_XX.txt`, or the input that was baked into the binary if that file does not exist
+    Default,
+    // A single file, or stdin if it is `-`
+    File(PathBuf),
+    // `dir/examples/day_XX_N.txt`, an example from the puzzle text
+    Example(u8),
+    // `dir/<name>/day_XX.txt`, the inputs of another account
+    Profile(String),
+}
+
+// Where the puzzle inputs come from.
+pub struct Loader {
+    dir: PathBuf,
+    source: Source,
+}
+
+impl Loader {
+    pub fn new(dir: PathBuf, source: Source) -> Loader {
+        Loader { dir, source }
+    }
+
+    // Name for the input that is loaded, so that expected answers can be kept for more than one
+    // input. An explicit file is named after its file stem, the examples are `example-N` and a
+    // profile has its own name.
+    pub fn id(&self) -> String {
+        match &self.source {
+            Source::Default => DEFAULT_ID.to_string(),
+            Source::File(file) if file.as_os_str() == STDIN => "stdin".to_string(),
+            Source::File(file) => file
+                .file_stem()
+                .map_or(String::new(), |s| s.to_string_lossy().into_owned()),
+            Source::Example(example) => format!("example-{example}"),
+            Source::Profile(name) => name.clone(),
+        }
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        let path = match &self.source {
+            Source::Default => {
+                let path = self.dir.join(file_name(day));
+                let res = read_file(day, &path);
+
+                if let Err(InputError::NotFound { .. }) = res {
+                    if let Some(text) = embedded(day) {
+                        return Ok(text.to_string());
+                    }
+                }
+
+                return res;
+            }
+            Source::File(file) if file.as_os_str() == STDIN => return read_stdin(),
+            Source::File(file) => file.clone(),
+            Source::Example(example) => self
+                .dir
+                .join(EXAMPLES_DIR)
+                .join(example_file_name(day, *example)),
+            Source::Profile(name) => self.dir.join(name).join(file_name(day)),
+        };
+
+        read_file(day, &path)
+    }
+
+    // The file the input of `day` is read from, so it can be read bit by bit instead of all at
+    // once. `None` if it does not come from a file, or the file does not exist or is empty.
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        let path = match &self.source {
+            Source::Default => self.dir.join(file_name(day)),
+            Source::File(file) if file.as_os_str() == STDIN => return None,
+            Source::File(file) => file.clone(),
+            Source::Example(example) => self
+                .dir
+                .join(EXAMPLES_DIR)
+                .join(example_file_name(day, *example)),
+            Source::Profile(name) => self.dir.join(name).join(file_name(day)),
+        };
+
+        let has_input = path.metadata().is_ok_and(|m| m.is_file() && m.len() > 0);
+        has_input.then_some(path)
+    }
+}
+
+// The names of the input profiles, which are the directories in `dir` other than the examples and
+// the bigboy inputs.
+pub fn profiles(dir: &Path) -> Result<Vec<String>, InputError> {
+    let io_error = |source| InputError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    let mut names = Vec::new();
+
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+
+        if !entry.file_type().map_err(io_error)?.is_dir() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name != EXAMPLES_DIR && name != BIGBOY {
+            names.push(name);
+        }
+    }
+
+    names.sort();
+
+    Ok(names)
+}
+
+// An empty file is a placeholder for an input that is not there yet, so it counts as missing.
+fn read_file(day: u8, path: &Path) -> Result<String, InputError> {
+    let text = std::fs::read_to_string(path).map_err(|source| {
+        if source.kind() == io::ErrorKind::NotFound {
+            InputError::NotFound {
+                day,
+                path: path.to_path_buf(),
+            }
+        } else {
+            InputError::Io {
+                path: path.to_path_buf(),
+                source,
+            }
+        }
+    })?;
+
+    if text.is_empty() {
+        return Err(InputError::NotFound {
+            day,
+            path: path.to_path_buf(),
+        });
+    }
+
+    Ok(text)
+}
+
+fn read_stdin() -> Result<String, InputError> {
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .map_err(|source| InputError::Io {
+            path: PathBuf::from("<stdin>"),
+            source,
+        })?;
+    Ok(text)
+}
+
+#[cfg(feature = "embedded-inputs")]
+fn embedded(day: u8) -> Option<&'static str> {
+    let text = match day {
+        1 => include_str!("../input/day_01.txt"),
+        2 => include_str!("../input/day_02.txt"),
+        3 => include_str!("../input/day_03.txt"),
+        4 => include_str!("../input/day_04.txt"),
+        5 => include_str!("../input/day_05.txt"),
+        6 => include_str!("../input/day_06.txt"),
+        7 => include_str!("../input/day_07.txt"),
+        8 => include_str!("../input/day_08.txt"),
+        9 => include_str!("../input/day_09.txt"),
+        10 => include_str!("../input/day_10.txt"),
+        11 => include_str!("../input/day_11.txt"),
+        12 => include_str!("../input/day_12.txt"),
+        13 => include_str!("../input/day_13.txt"),
+        14 => include_str!("../input/day_14.txt"),
+        15 => include_str!("../input/day_15.txt"),
+        16 => include_str!("../input/day_16.txt"),
+        17 => include_str!("../input/day_17.txt"),
+        18 => include_str!("../input/day_18.txt"),
+        19 => include_str!("../input/day_19.txt"),
+        20 => include_str!("../input/day_20.txt"),
+        21 => include_str!("../input/day_21.txt"),
+        22 => include_str!("../input/day_22.txt"),
+        23 => include_str!("../input/day_23.txt"),
+        24 => include_str!("../input/day_24.txt"),
+        25 => include_str!("../input/day_25.txt"),
+        _ => return None,
+    };
+
+    Some(text).filter(|text| !text.is_empty())
+}
+
+#[cfg(not(feature = "embedded-inputs"))]
+fn embedded(_day: u8) -> Option<&'static str> {
+    None
+}
@@ -0,0 +1,53 @@
+// The solvers of every day and everything to run, check and benchmark them. The `aoc2023` binary
+// is a command line interface over this.
+
+pub mod answer;
+pub mod aoc;
+pub mod baseline;
+pub mod bench;
+pub mod budget;
+pub mod cache;
+pub mod commands;
+pub mod error;
+pub mod generate;
+pub mod graph;
+pub mod http;
+pub mod input;
+pub mod memory;
+pub mod output;
+pub mod paths;
+pub mod registry;
+pub mod run;
+pub mod runner;
+pub mod scaffold;
+pub mod scale;
+pub mod stats;
+pub mod submissions;
+pub mod util;
+pub mod verify;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
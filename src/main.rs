@@ -1,287 +1,318 @@
-use std::hint::black_box;
-use std::time::{Duration, Instant};
-
-mod util;
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-
-use clap::Parser;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc2023::cache::{self, Cache};
+use aoc2023::input::{self, Source};
+use aoc2023::output::Format;
+use aoc2023::registry::{self, Days};
+use aoc2023::runner::{self, Report};
+use aoc2023::{baseline, bench, commands, generate, run, scaffold, scale, verify};
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
     day: Option<u8>,
 
-    #[arg(short, long, requires("day"), value_parser = clap::value_parser!(u8).range(1..=2))]
+    /// Days to run, like `all`, `10-16` or `1,3,5`, where every day is run as if running all days
+    #[arg(long, conflicts_with = "day", value_name = "DAYS")]
+    days: Option<Days>,
+
+    /// Only run this part of the selected days
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
     part: Option<u8>,
 
     #[arg(short, long, default_value_t = false)]
     bench: bool,
-}
 
-fn input(day: u8) -> &'static str {
-    match day {
-        1 => include_str!("../input/day_01.txt"),
-        2 => include_str!("../input/day_02.txt"),
-        3 => include_str!("../input/day_03.txt"),
-        4 => include_str!("../input/day_04.txt"),
-        5 => include_str!("../input/day_05.txt"),
-        6 => include_str!("../input/day_06.txt"),
-        7 => include_str!("../input/day_07.txt"),
-        8 => include_str!("../input/day_08.txt"),
-        9 => include_str!("../input/day_09.txt"),
-        10 => include_str!("../input/day_10.txt"),
-        11 => include_str!("../input/day_11.txt"),
-        12 => include_str!("../input/day_12.txt"),
-        13 => include_str!("../input/day_13.txt"),
-        14 => include_str!("../input/day_14.txt"),
-        15 => include_str!("../input/day_15.txt"),
-        16 => include_str!("../input/day_16.txt"),
-        17 => include_str!("../input/day_17.txt"),
-        18 => include_str!("../input/day_18.txt"),
-        19 => include_str!("../input/day_19.txt"),
-        20 => include_str!("../input/day_20.txt"),
-        21 => include_str!("../input/day_21.txt"),
-        22 => include_str!("../input/day_22.txt"),
-        23 => include_str!("../input/day_23.txt"),
-        24 => include_str!("../input/day_24.txt"),
-        _ => todo!(),
-    }
+    /// Read the puzzle input from this file instead, or from stdin if it is `-`
+    #[arg(short, long, requires("day"))]
+    input: Option<PathBuf>,
+
+    /// Run on the given example from the puzzle text instead, read from `examples/day_XX_N.txt` in
+    /// the input directory
+    #[arg(long, conflicts_with = "input", num_args = 0..=1, default_missing_value = "1", value_name = "N")]
+    example: Option<u8>,
+
+    /// Read the puzzle inputs of this profile, from `<NAME>/day_XX.txt` in the input directory
+    #[arg(long, conflicts_with_all = ["input", "example"], value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Run on the scaled up inputs in `bigboy/day_XX.txt` in the input directory, which the parts
+    /// that can read them a line at a time do without loading them at once
+    #[arg(long, default_value_t = false, conflicts_with_all = ["input", "example", "profile"])]
+    bigboy: bool,
+
+    /// Run every part on the inputs of every profile and show which ones fail
+    #[arg(long, default_value_t = false, conflicts_with_all = ["input", "example", "profile", "bigboy", "bench"])]
+    profiles: bool,
+
+    /// Directory that contains the `day_XX.txt` puzzle inputs
+    #[arg(long, global = true, default_value = input::DEFAULT_DIR)]
+    input_dir: PathBuf,
+
+    /// Also run the parts that are known to be slow when running multiple days
+    #[arg(long, default_value_t = false)]
+    slow: bool,
+
+    /// Number of parts to run at the same time when running all days
+    #[arg(long, default_value_t = 1, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    jobs: usize,
+
+    /// Give up on a part after this many seconds, 0 means no limit. A part that runs over can not
+    /// be stopped, it keeps running in the background until the program exits.
+    #[arg(long, default_value = "30", value_name = "SECONDS", value_parser = parse_seconds)]
+    timeout: Duration,
+
+    /// Seconds to spend on benchmarking each part, including warming up
+    #[arg(long, default_value = "0.5", value_name = "SECONDS", value_parser = parse_seconds)]
+    bench_time: Duration,
+
+    /// Check the benchmark results against a time budget for all days together
+    #[arg(long, requires("bench"), num_args = 0..=1, default_missing_value = "1", value_name = "SECONDS", value_parser = parse_seconds)]
+    budget: Option<Duration>,
+
+    /// Save the benchmark results under this name, to compare against later
+    #[arg(long, requires("bench"), value_name = "NAME")]
+    save_baseline: Option<String>,
+
+    /// Compare the benchmark results against the ones saved under this name
+    #[arg(long, requires("bench"), value_name = "NAME")]
+    baseline: Option<String>,
+
+    /// How to print the answers or benchmark results
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Check the answers against the expected answers instead of printing them
+    #[arg(long, default_value_t = false, conflicts_with = "bench")]
+    verify: bool,
+
+    /// File with the expected answers for --verify
+    #[arg(long, default_value = verify::DEFAULT_PATH)]
+    answers: PathBuf,
+
+    /// Run every part instead of printing the answers that were cached by earlier runs, without
+    /// reading or writing the cache
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
 }
 
-fn run(day: u8, part: u8) {
-    macro_rules! match_run_day {
-        ($day:expr, $day_name:ident) => {
-            match_run_day!($day, $day_name, 1, part1);
-            match_run_day!($day, $day_name, 2, part2);
-        };
-
-        ($day:expr, $day_name:ident, $part:expr, $part_name:ident) => {
-            if day == $day && part == $part {
-                let answer = $day_name::$part_name(input(day));
-                println!("Day {day:2}, part {part} = {answer}");
-                return;
-            }
-        };
-    }
-
-    match_run_day!(1, day01);
-    match_run_day!(2, day02);
-    match_run_day!(3, day03);
-    match_run_day!(4, day04);
-    match_run_day!(5, day05);
-    match_run_day!(6, day06);
-    match_run_day!(7, day07);
-    match_run_day!(8, day08);
-    match_run_day!(9, day09);
-    match_run_day!(10, day10);
-    match_run_day!(11, day11);
-    match_run_day!(12, day12);
-    match_run_day!(13, day13);
-    match_run_day!(14, day14);
-    match_run_day!(15, day15);
-    match_run_day!(16, day16);
-    match_run_day!(17, day17);
-    match_run_day!(18, day18);
-    match_run_day!(19, day19);
-    match_run_day!(20, day20);
-    match_run_day!(21, day21);
-    match_run_day!(22, day22);
-    match_run_day!(23, day23);
-    match_run_day!(24, day24);
-
-    todo!()
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Download the puzzle input of a day into the input directory, using the session token from
+    /// AOC_SESSION or the config file
+    Fetch {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+
+    /// Send an answer to the site and show what it says about it. The answer is computed from the
+    /// input unless it is given, which only works for the parts that are implemented.
+    Submit {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        #[arg(short, long)]
+        answer: Option<String>,
+    },
+
+    /// Create `src/dayXX.rs` with stubs for both parts and register it, along with empty files for
+    /// its input and first example
+    New {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+
+    /// Write a scaled up version of the puzzle input of a day to `bigboy/day_XX.txt` in the input
+    /// directory, to run with --bigboy
+    Scale {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// How many times bigger to make the input
+        #[arg(short, long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        factor: usize,
+    },
+
+    /// Print a made up input for a day, with the answers that are known for it on stderr
+    Gen {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Every seed gives a different input, and the same seed always gives the same one
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+
+        /// How big to make the input, bigger sizes leave more answers unknown
+        #[arg(long, default_value_t = generate::DEFAULT_SIZE, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        size: usize,
+    },
 }
 
-fn bench(day: u8, part: u8, sample_count: usize) -> Vec<Duration> {
-    macro_rules! match_day {
-        ($day:expr, $day_name:ident) => {
-            match_day!($day, $day_name, 1, part1);
-            match_day!($day, $day_name, 2, part2);
-        };
-
-        ($day:expr, $day_name:ident, $part:expr, $part_name:ident) => {
-            if day == $day && part == $part {
-                let text = input(day);
-                let mut samples = Vec::new();
-
-                for _ in 0..sample_count {
-                    let start = Instant::now();
-                    let _ = black_box($day_name::$part_name(black_box(text)));
-                    let t = start.elapsed();
-                    samples.push(t);
-                }
-
-                return samples;
-            }
-        };
-    }
-
-    match_day!(1, day01);
-    match_day!(2, day02);
-    match_day!(3, day03);
-    match_day!(4, day04);
-    match_day!(5, day05);
-    match_day!(6, day06);
-    match_day!(7, day07);
-    match_day!(8, day08);
-    match_day!(9, day09);
-    match_day!(10, day10);
-    match_day!(11, day11);
-    match_day!(12, day12);
-    match_day!(13, day13);
-    match_day!(14, day14);
-    match_day!(15, day15);
-    match_day!(16, day16);
-    if day == 17 || (day == 21 && part == 2) {
-        return vec![std::time::Duration::from_millis(0); sample_count];
-    }
-    //match_day!(17, day17);
-    match_day!(18, day18);
-    match_day!(19, day19);
-    match_day!(20, day20);
-    match_day!(21, day21);
-
-    todo!()
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("`{s}` is not a valid number of seconds"))
 }
 
-fn print_time(secs: f64) {
-    if secs > 1.0 {
-        print!("{:>3.0}s", secs);
-    } else if secs > 0.001 {
-        print!("{:>3.0}ms", secs * 1_000.0);
-    } else if secs > 0.000_001 {
-        print!("{:>3.0}μs", secs * 1_000_000.0);
-    } else {
-        print!("{:>3.0}ns", secs * 1_000_000_000.0);
-    }
+// Prints the error and stops with a failure.
+fn fail(e: impl std::fmt::Display) -> ! {
+    eprintln!("error: {e}");
+    std::process::exit(1);
 }
 
-#[derive(Debug)]
-struct Benchmark {
-    name: String,
-    mean: f64,
-    stddev: f64,
-    percentage: f64,
-}
+fn main() {
+    let args = Args::parse();
 
-impl Benchmark {
-    fn from_samples(name: String, samples: Vec<Duration>) -> Benchmark {
-        let mut samples = samples;
-        samples.sort();
-
-        // Only keep the best 90% of samples to remove outliers.
-        samples.truncate((samples.len() * 10) / 9);
-
-        let n = samples.len() as f64;
-        let sum = samples.iter().map(|d| d.as_secs_f64()).sum::<f64>();
-        let mean = sum / n;
-        let stddev = {
-            let s = samples
-                .iter()
-                .map(|d| (d.as_secs_f64() - mean).powf(2.0))
-                .sum::<f64>();
-            (s / n).sqrt()
+    runner::install_panic_hook();
+
+    if let Some(command) = &args.command {
+        let res = match *command {
+            Command::Fetch { day } => commands::fetch(&args.input_dir, day)
+                .map(|path| println!("Saved the input of day {day} to {}", path.display())),
+            Command::Submit {
+                day,
+                part,
+                ref answer,
+            } => {
+                let loader = input::Loader::new(args.input_dir.clone(), Source::Default);
+                let limit = (!args.timeout.is_zero()).then_some(args.timeout);
+                commands::submit(&loader, day, part, answer.clone(), limit)
+            }
+            Command::New { day } => scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day)
+                .map(|changed| {
+                    for path in changed {
+                        println!("Wrote {}", path.display());
+                    }
+                    println!(
+                        "Both parts of day {day} are registered as unimplemented, change their \
+                             status in src/registry.rs once they work"
+                    );
+                })
+                .map_err(|e| e.to_string()),
+            Command::Scale { day, factor } => scale::scale(&args.input_dir, day, factor)
+                .map(|path| println!("Wrote {}", path.display()))
+                .map_err(|e| e.to_string()),
+            Command::Gen { day, seed, size } => commands::gen(day, seed, size),
         };
 
-        Benchmark {
-            name,
-            mean,
-            stddev,
-            percentage: 0.0,
+        if let Err(e) = res {
+            fail(e);
         }
-    }
 
-    fn print(&self) {
-        print!("{} | ", self.name);
-        print_time(self.mean);
-        print!(" +- ");
-        print_time(self.stddev);
-        println!(" | {:>4.1}%", self.percentage);
+        return;
     }
-}
-
-fn main() {
-    let args = Args::parse();
 
-    let sample_count = 10;
-    let max_day = 21;
+    let source = match (args.input, args.example, args.profile) {
+        _ if args.bigboy => Source::Profile(input::BIGBOY.to_string()),
+        (Some(file), _, _) => Source::File(file),
+        (None, Some(example), _) => Source::Example(example),
+        (None, None, Some(name)) => Source::Profile(name),
+        (None, None, None) => Source::Default,
+    };
+    let loader = input::Loader::new(args.input_dir.clone(), source);
+
+    // The other formats print a single document, which the budget, comparison and the table of
+    // profiles do not fit in.
+    if args.format != Format::Text
+        && (args.budget.is_some() || args.baseline.is_some() || args.profiles)
+    {
+        fail("--budget, --baseline and --profiles can only be used with --format text");
+    }
 
-    if args.bench {
-        let mut benchmarks = Vec::new();
-
-        for day in 1..=max_day {
-            for part in 1..=2 {
-                let samples = bench(day, part, sample_count);
-                benchmarks.push(Benchmark::from_samples(
-                    format!("Day {day:2}, part {part}"),
-                    samples,
-                ));
-            }
-        }
+    let options = run::Options {
+        days: match (args.day, args.days) {
+            (Some(day), _) => Days::single(day),
+            (None, Some(days)) => days,
+            (None, None) => Days::ALL,
+        },
+        part: args.part,
+        // Like when running, slow parts are included when a day is picked explicitly.
+        include_slow: args.slow || args.day.is_some(),
+        limit: (!args.timeout.is_zero()).then_some(args.timeout),
+        jobs: args.jobs,
+        format: args.format,
+        stream: args.bigboy,
+    };
 
-        let total_time = benchmarks.iter().fold(0.0, |acc, x| acc + x.mean);
+    let answers = args
+        .verify
+        .then(|| verify::Answers::load(&args.answers).unwrap_or_else(|e| fail(e)));
 
-        for x in benchmarks.iter_mut() {
-            x.percentage = x.mean * 100.0 / total_time;
-        }
+    if args.profiles {
+        let profiles =
+            run::profiles(&args.input_dir, answers.as_ref(), &options).unwrap_or_else(|e| fail(e));
 
-        for x in benchmarks {
-            x.print();
+        if profiles.iter().flat_map(|(_, r)| r).any(|r| r.failed) {
+            std::process::exit(1);
         }
 
-        print!("\nTotal time taken: ");
-        print_time(total_time);
-        println!();
-
         return;
     }
 
-    if args.day.is_none() {
-        // Run all the days
-        for day in 1..=25 {
-            for part in 1..=2 {
-                run(day, part);
-            }
+    if let Some(answers) = &answers {
+        let solvers = registry::select(options.days, options.part);
+        let checks = verify::verify(
+            solvers,
+            &loader,
+            answers,
+            options.include_slow,
+            options.limit,
+        );
+
+        verify::print_table(&checks);
+
+        if checks.iter().any(|c| c.verdict.is_failure()) {
+            std::process::exit(1);
         }
 
         return;
     }
 
-    let Some(day) = args.day else { unreachable!() };
+    if args.bench {
+        // Load the baseline first, so a typo in its name does not waste a whole benchmark run.
+        let baseline = args
+            .baseline
+            .as_deref()
+            .map(|name| baseline::load(name).unwrap_or_else(|e| fail(e)));
+
+        let settings = bench::Settings::with_time(args.bench_time);
+        let benchmarks = run::bench(&loader, &options, &settings, args.budget, baseline.as_ref());
+
+        if let Some(name) = &args.save_baseline {
+            let path = baseline::save(name, &benchmarks).unwrap_or_else(|e| fail(e));
+            eprintln!("\nSaved baseline `{name}` to {}", path.display());
+        }
 
-    if args.part.is_none() {
-        run(day, 1);
-        run(day, 2);
         return;
     }
 
-    let Some(part) = args.part else {
-        unreachable!()
+    // A cache that can not be read only costs the time to compute its answers again.
+    let mut cache = (!args.no_cache).then(|| {
+        let path = cache::default_path();
+        Cache::load(&path).unwrap_or_else(|e| {
+            eprintln!("warning: {e}, starting with an empty cache");
+            Cache::new(&path)
+        })
+    });
+
+    // The bigboy inputs can be too big to load, so they take this way even for a single day.
+    let reports = match args.day {
+        Some(day) if !args.bigboy => {
+            run::run_day(&loader, cache.as_mut(), day, &options).unwrap_or_else(|e| fail(e))
+        }
+        _ => run::run_all(&loader, cache.as_mut(), &options),
     };
 
-    run(day, part);
+    if reports.iter().any(Report::is_failure) {
+        std::process::exit(1);
+    }
 }
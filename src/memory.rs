@@ -0,0 +1,133 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Whether allocations are counted, which is when the `count-allocations` feature is enabled. The
+// counting allocator then replaces the global allocator of everything linking this crate.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+// The system allocator, keeping count of what goes through it. The counters are shared by all
+// threads.
+pub struct Counting;
+
+fn grow(size: usize) {
+    let live = LIVE.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+fn shrink(size: usize) {
+    LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+}
+
+fn count(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+    grow(size);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    // Counted as a new allocation of `new_size`, because that is what it costs when the block can
+    // not grow in place.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED.fetch_add(new_size as u64, Ordering::Relaxed);
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new
+    }
+}
+
+// What a single run allocated.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Usage {
+    pub allocations: u64,
+    // Bytes allocated in total, including the ones that were freed again
+    pub bytes: u64,
+    // Most bytes that were allocated at the same time, on top of what was already allocated
+    pub peak: u64,
+}
+
+// Runs `f` and counts its allocations, or returns `None` without running it if allocations are not
+// counted. Allocations by other threads are counted too, so nothing else should run meanwhile.
+pub fn measure<T>(f: impl FnOnce() -> T) -> Option<Usage> {
+    if !ENABLED {
+        return None;
+    }
+
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+
+    // The result is only dropped after measuring, so freeing it does not count.
+    let result = f();
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+
+    drop(result);
+    Some(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::measure;
+
+    #[cfg(not(feature = "count-allocations"))]
+    #[test]
+    fn nothing_is_measured_without_the_feature() {
+        assert_eq!(measure(|| vec![0u8; 1024]), None);
+    }
+
+    // Other tests run at the same time, so their allocations may be counted as well.
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn allocations_are_counted() {
+        let usage = measure(|| {
+            let v = vec![1u8; 1 << 20];
+            let w = v.clone();
+            drop(v);
+            w.len()
+        })
+        .unwrap();
+
+        assert!(usage.allocations >= 2);
+        assert!(usage.bytes >= 2 << 20);
+        assert!(usage.peak >= 2 << 20);
+    }
+}
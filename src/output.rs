@@ -0,0 +1,306 @@
+use std::fmt::Write;
+
+use crate::bench::{format_bytes, format_time, Benchmark};
+use crate::memory::Usage;
+use crate::runner::Report;
+
+// How the answers and benchmark results are printed. Everything except `Text` prints a single
+// document to stdout, so it can be read by scripts or pasted into the README.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, clap::ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+// Column names of the answers, in the order they are printed.
+const ANSWER_COLUMNS: [&str; 6] = ["day", "part", "status", "answer", "detail", "time_ns"];
+
+// Column names of the benchmark results, in the order they are printed. All times are in
+// nanoseconds. The allocations are only known with the `count-allocations` feature.
+const BENCHMARK_COLUMNS: [&str; 15] = [
+    "day",
+    "part",
+    "mean_ns",
+    "stddev_ns",
+    "median_ns",
+    "min_ns",
+    "p95_ns",
+    "ci_low_ns",
+    "ci_high_ns",
+    "outliers",
+    "samples",
+    "share",
+    "allocations",
+    "allocated_bytes",
+    "peak_bytes",
+];
+
+// A single cell, which decides how it is quoted.
+enum Value {
+    Number(String),
+    Text(String),
+    Null,
+}
+
+fn nanos(secs: f64) -> Value {
+    Value::Number(format!("{:.0}", secs * 1e9))
+}
+
+fn answer_row(r: &Report) -> [Value; 6] {
+    [
+        Value::Number(r.day.to_string()),
+        Value::Number(r.part.to_string()),
+        Value::Text(r.label.to_string()),
+        r.answer
+            .as_ref()
+            .map_or(Value::Null, |a| Value::Text(a.to_string())),
+        Value::Text(r.detail.clone()),
+        r.time
+            .map_or(Value::Null, |t| Value::Number(t.as_nanos().to_string())),
+    ]
+}
+
+fn benchmark_row(b: &Benchmark) -> [Value; 15] {
+    let s = &b.stats;
+    let memory = |f: fn(&Usage) -> u64| {
+        b.memory
+            .as_ref()
+            .map_or(Value::Null, |m| Value::Number(f(m).to_string()))
+    };
+
+    [
+        Value::Number(b.day.to_string()),
+        // Parsing is not a part, so it gets a name instead
+        b.part.map_or(Value::Text("parse".to_string()), |p| {
+            Value::Number(p.to_string())
+        }),
+        nanos(s.mean),
+        nanos(s.stddev),
+        nanos(s.median),
+        nanos(s.min),
+        nanos(s.p95),
+        nanos(s.ci.0),
+        nanos(s.ci.1),
+        Value::Number(s.outliers.to_string()),
+        Value::Number(s.samples.len().to_string()),
+        Value::Number(format!("{:.2}", b.percentage)),
+        memory(|m| m.allocations),
+        memory(|m| m.bytes),
+        memory(|m| m.peak),
+    ]
+}
+
+pub fn print_answers(format: Format, reports: &[Report]) {
+    let rows = reports.iter().map(answer_row).collect::<Vec<_>>();
+
+    match format {
+        Format::Json => print!("{}", json(&ANSWER_COLUMNS, &rows)),
+        Format::Csv => print!("{}", csv(&ANSWER_COLUMNS, &rows)),
+        Format::Markdown => {
+            let rows = reports
+                .iter()
+                .map(|r| {
+                    let answer = match &r.answer {
+                        Some(answer) => answer.to_string(),
+                        None => r.detail.lines().next().unwrap_or("").to_string(),
+                    };
+                    let time = r.time.map_or(String::new(), |t| {
+                        format_time(t.as_secs_f64()).trim().to_string()
+                    });
+
+                    vec![
+                        r.day.to_string(),
+                        r.part.to_string(),
+                        r.label.to_string(),
+                        answer,
+                        time,
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+            print!(
+                "{}",
+                markdown(&["Day", "Part", "Status", "Answer", "Time"], &rows)
+            );
+        }
+        Format::Text => unreachable!("text is printed while running"),
+    }
+}
+
+pub fn print_benchmarks(format: Format, benchmarks: &[Benchmark]) {
+    let rows = benchmarks.iter().map(benchmark_row).collect::<Vec<_>>();
+
+    match format {
+        Format::Json => print!("{}", json(&BENCHMARK_COLUMNS, &rows)),
+        Format::Csv => print!("{}", csv(&BENCHMARK_COLUMNS, &rows)),
+        Format::Markdown => {
+            let time = |secs: f64| format_time(secs).trim().to_string();
+            let bytes = |bytes: u64| format_bytes(bytes).trim().to_string();
+            let counted = benchmarks.iter().any(|b| b.memory.is_some());
+
+            let mut rows = benchmarks
+                .iter()
+                .map(|b| {
+                    let s = &b.stats;
+
+                    let mut row = vec![
+                        b.day.to_string(),
+                        b.part.map_or("parse".to_string(), |p| p.to_string()),
+                        time(s.mean),
+                        time(s.stddev),
+                        time(s.median),
+                        time(s.min),
+                        time(s.p95),
+                        format!("{:.1}%", b.percentage),
+                    ];
+
+                    if counted {
+                        row.extend(match &b.memory {
+                            Some(m) => [m.allocations.to_string(), bytes(m.bytes), bytes(m.peak)],
+                            None => Default::default(),
+                        });
+                    }
+
+                    row
+                })
+                .collect::<Vec<_>>();
+
+            let total = benchmarks.iter().map(|b| b.stats.mean).sum::<f64>();
+            let mut columns = vec![
+                "Day", "Part", "Mean", "Std dev", "Median", "Min", "p95", "Share",
+            ];
+            if counted {
+                columns.extend(["Allocations", "Allocated", "Peak"]);
+            }
+
+            let mut total_row = vec![String::new(); columns.len()];
+            total_row[0] = "**Total**".to_string();
+            total_row[2] = format!("**{}**", time(total));
+            rows.push(total_row);
+
+            print!("{}", markdown(&columns, &rows));
+        }
+        Format::Text => unreachable!("text is printed by `Benchmark::print`"),
+    }
+}
+
+// An array with an object per row.
+fn json<const N: usize>(columns: &[&str; N], rows: &[[Value; N]]) -> String {
+    let mut out = String::from("[");
+
+    for (i, row) in rows.iter().enumerate() {
+        out += if i == 0 { "\n  {" } else { ",\n  {" };
+
+        for (j, (column, value)) in columns.iter().zip(row).enumerate() {
+            let sep = if j == 0 { "" } else { ", " };
+            let value = match value {
+                Value::Number(n) => n.clone(),
+                Value::Text(s) => json_string(s),
+                Value::Null => "null".to_string(),
+            };
+            write!(out, "{sep}\"{column}\": {value}").unwrap();
+        }
+
+        out += "}";
+    }
+
+    out += if rows.is_empty() { "]\n" } else { "\n]\n" };
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+// A header line followed by a line per row, quoted as described in RFC 4180. Missing values are
+// left empty.
+fn csv<const N: usize>(columns: &[&str; N], rows: &[[Value; N]]) -> String {
+    let mut out = columns.join(",") + "\n";
+
+    for row in rows {
+        let cells = row
+            .iter()
+            .map(|value| match value {
+                Value::Number(n) => n.clone(),
+                Value::Text(s) if s.contains([',', '"', '\n', '\r']) => {
+                    format!("\"{}\"", s.replace('"', "\"\""))
+                }
+                Value::Text(s) => s.clone(),
+                Value::Null => String::new(),
+            })
+            .collect::<Vec<_>>();
+
+        out += &cells.join(",");
+        out += "\n";
+    }
+
+    out
+}
+
+fn markdown(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let escape = |cell: &str| cell.replace('|', "\\|");
+
+    let mut out = line(columns.iter().map(|c| escape(c)).collect());
+    out += &line(vec!["---".to_string(); columns.len()]);
+
+    for row in rows {
+        out += &line(row.iter().map(|c| escape(c)).collect());
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv, json, Value};
+
+    fn rows() -> Vec<[Value; 3]> {
+        vec![
+            [
+                Value::Number("1".into()),
+                Value::Text("a, \"b\"".into()),
+                Value::Null,
+            ],
+            [
+                Value::Number("2".into()),
+                Value::Text("line\nbreak".into()),
+                Value::Number("3".into()),
+            ],
+        ]
+    }
+
+    #[test]
+    fn json_escapes_strings() {
+        assert_eq!(
+            json(&["n", "s", "x"], &rows()),
+            "[\n  {\"n\": 1, \"s\": \"a, \\\"b\\\"\", \"x\": null},\n  {\"n\": 2, \"s\": \"line\\nbreak\", \"x\": 3}\n]\n"
+        );
+        assert_eq!(json(&["n"], &[]), "[]\n");
+    }
+
+    #[test]
+    fn csv_quotes_fields() {
+        assert_eq!(
+            csv(&["n", "s", "x"], &rows()),
+            "n,s,x\n1,\"a, \"\"b\"\"\",\n2,\"line\nbreak\",3\n"
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use std::path::{Path, PathBuf};
+
+// The directory that the baselines, the answer cache and the submissions are kept in. It is
+// `CARGO_TARGET_DIR` if that is set and else the `target` directory of the crate, both taken from
+// the root of the crate, so the same one is used whatever directory the binary is run from.
+pub fn target_dir() -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => root.join(dir),
+        None => root.join("target"),
+    }
+}
@@ -0,0 +1,281 @@
+use std::any::Any;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::answer::Answer;
+use crate::error::SolveError;
+use crate::util::Lines;
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    Ok,
+    // Gives an answer, but takes far longer than the rest. These are skipped unless asked for.
+    Slow,
+    // Work in progress. Running these panics, never finishes or gives a wrong answer.
+    Unimplemented,
+}
+
+// The parsed input of a day, whatever type its `parse` returns.
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+// Days can opt in to parsing their input separately from solving it, by providing a `parse`
+// function and having the parts take its result. The parse and solve time can then be measured on
+// their own.
+pub struct Phases {
+    pub parse: fn(&str) -> Result<Parsed, SolveError>,
+    pub solve: fn(&(dyn Any + Send + Sync)) -> Result<Answer, SolveError>,
+}
+
+// Solves a part while reading the input a line at a time.
+pub type Stream = fn(&mut dyn BufRead) -> Result<Answer, SolveError>;
+
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub status: Status,
+    // Parses the input and solves the part in one go
+    pub run: fn(&str) -> Result<Answer, SolveError>,
+    pub phases: Option<Phases>,
+    // For the days that can read their input a line at a time
+    pub stream: Option<Stream>,
+    // Source code of the day, so cached answers can tell when it changed
+    pub source: &'static str,
+}
+
+macro_rules! solver {
+    ($day:literal, $part:literal, $module:ident :: $func:ident) => {
+        solver!($day, $part, $module::$func, Ok)
+    };
+
+    // Days that only need to see one line at a time take `Lines`, so they can also be given inputs
+    // that are too large to read into memory.
+    ($day:literal, $part:literal, $module:ident :: $func:ident, streamed) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: concat!(stringify!($module), "::", stringify!($func)),
+            status: Status::Ok,
+            run: |input| {
+                $module::$func(&mut Lines::from(input))
+                    .map(Answer::from)
+                    .map_err(|e| e.locate($day, input))
+            },
+            phases: None,
+            stream: Some(|reader| {
+                $module::$func(&mut Lines::reader(reader))
+                    .map(Answer::from)
+                    .map_err(|e| e.locate($day, ""))
+            }),
+            source: include_str!(concat!(stringify!($module), ".rs")),
+        }
+    };
+
+    ($day:literal, $part:literal, $module:ident :: $func:ident, $status:ident) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: concat!(stringify!($module), "::", stringify!($func)),
+            status: Status::$status,
+            run: |input| {
+                $module::$func(input)
+                    .map(Answer::from)
+                    .map_err(|e| e.locate($day, input))
+            },
+            phases: None,
+            stream: None,
+            source: include_str!(concat!(stringify!($module), ".rs")),
+        }
+    };
+
+    ($day:literal, $part:literal, $module:ident :: $parse:ident => $func:ident) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: concat!(stringify!($module), "::", stringify!($func)),
+            status: Status::Ok,
+            run: |input| {
+                $module::$parse(input)
+                    .and_then(|parsed| $module::$func(&parsed))
+                    .map(Answer::from)
+                    .map_err(|e| e.locate($day, input))
+            },
+            phases: Some(Phases {
+                parse: |input| {
+                    $module::$parse(input)
+                        .map(|parsed| Box::new(parsed) as Parsed)
+                        .map_err(|e| e.locate($day, input))
+                },
+                // Errors found while solving can not point into the input anymore.
+                solve: |parsed| {
+                    $module::$func(parsed.downcast_ref().expect("input parsed by another day"))
+                        .map(Answer::from)
+                        .map_err(|e| e.locate($day, ""))
+                },
+            }),
+            stream: None,
+            source: include_str!(concat!(stringify!($module), ".rs")),
+        }
+    };
+}
+
+// Every solver, ordered by day and then by part. This is the only place a day has to be added to
+// be picked up by the runner and the benchmarks.
+pub static SOLVERS: &[Solver] = &[
+    solver!(1, 1, day01::part1, streamed),
+    solver!(1, 2, day01::part2, streamed),
+    solver!(2, 1, day02::part1, streamed),
+    solver!(2, 2, day02::part2, streamed),
+    solver!(3, 1, day03::parse => part1),
+    solver!(3, 2, day03::parse => part2),
+    solver!(4, 1, day04::part1, streamed),
+    solver!(4, 2, day04::part2, streamed),
+    solver!(5, 1, day05::part1),
+    solver!(5, 2, day05::part2),
+    solver!(6, 1, day06::part1),
+    solver!(6, 2, day06::part2),
+    solver!(7, 1, day07::part1),
+    solver!(7, 2, day07::part2),
+    solver!(8, 1, day08::part1),
+    solver!(8, 2, day08::part2),
+    solver!(9, 1, day09::part1, streamed),
+    solver!(9, 2, day09::part2, streamed),
+    solver!(10, 1, day10::part1),
+    solver!(10, 2, day10::part2),
+    solver!(11, 1, day11::part1),
+    solver!(11, 2, day11::part2),
+    solver!(12, 1, day12::part1, streamed),
+    solver!(12, 2, day12::part2, streamed),
+    solver!(13, 1, day13::part1),
+    solver!(13, 2, day13::part2),
+    solver!(14, 1, day14::part1),
+    solver!(14, 2, day14::part2),
+    solver!(15, 1, day15::part1),
+    solver!(15, 2, day15::part2),
+    solver!(16, 1, day16::part1),
+    solver!(16, 2, day16::part2),
+    solver!(17, 1, day17::part1, Slow),
+    solver!(17, 2, day17::part2, Slow),
+    solver!(18, 1, day18::part1),
+    solver!(18, 2, day18::part2),
+    solver!(19, 1, day19::part1),
+    solver!(19, 2, day19::part2),
+    solver!(20, 1, day20::part1),
+    solver!(20, 2, day20::part2, Slow),
+    solver!(21, 1, day21::part1),
+    solver!(21, 2, day21::part2, Unimplemented),
+    solver!(22, 1, day22::parse => part1),
+    solver!(22, 2, day22::parse => part2),
+    solver!(23, 1, day23::part1),
+    solver!(23, 2, day23::part2, Slow),
+    solver!(24, 1, day24::part1),
+    solver!(24, 2, day24::part2, Unimplemented),
+    solver!(25, 1, day25::parse => part1),
+];
+
+// All days that have at least one solver, in order.
+pub fn days() -> impl Iterator<Item = u8> {
+    let mut last = 0;
+    SOLVERS.iter().filter_map(move |s| {
+        let is_new = s.day != last;
+        last = s.day;
+        is_new.then_some(s.day)
+    })
+}
+
+// A set of days, written as `all` or as a comma separated list of days and ranges of days, for
+// example `1,3,10-16`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Days(u32);
+
+impl Days {
+    pub const ALL: Days = Days(((1 << 26) - 1) & !1);
+
+    pub fn single(day: u8) -> Days {
+        Days(1 << day)
+    }
+
+    pub fn contains(self, day: u8) -> bool {
+        day < 32 && self.0 & (1 << day) != 0
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Days, String> {
+        if s == "all" {
+            return Ok(Days::ALL);
+        }
+
+        let day = |s: &str| match s.trim().parse::<u8>() {
+            Ok(day @ 1..=25) => Ok(day),
+            _ => Err(format!("`{s}` is not a day between 1 and 25")),
+        };
+
+        let mut days = Days(0);
+
+        for item in s.split(',') {
+            let (first, last) = match item.split_once('-') {
+                Some((first, last)) => (day(first)?, day(last)?),
+                None => (day(item)?, day(item)?),
+            };
+
+            if first > last {
+                return Err(format!("`{item}` is an empty range"));
+            }
+
+            for d in first..=last {
+                days.0 |= 1 << d;
+            }
+        }
+
+        Ok(days)
+    }
+}
+
+// The solvers of the given days, optionally only for one part, in the same order as `SOLVERS`.
+pub fn select(days: Days, part: Option<u8>) -> impl Iterator<Item = &'static Solver> {
+    SOLVERS
+        .iter()
+        .filter(move |s| days.contains(s.day) && part.is_none_or(|part| s.part == part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Days;
+
+    #[test]
+    fn solvers_are_ordered_and_unique() {
+        for pair in super::SOLVERS.windows(2) {
+            assert!((pair[0].day, pair[0].part) < (pair[1].day, pair[1].part));
+        }
+    }
+
+    #[test]
+    fn solver_parts_are_valid() {
+        for s in super::SOLVERS {
+            assert!((1..=25).contains(&s.day), "{}", s.name);
+            assert!((1..=2).contains(&s.part), "{}", s.name);
+        }
+    }
+
+    #[test]
+    fn parse_days() {
+        let days = "1,3,10-12".parse::<Days>().unwrap();
+        let listed = (0..=26).filter(|&d| days.contains(d)).collect::<Vec<_>>();
+
+        assert_eq!(listed, [1, 3, 10, 11, 12]);
+        assert_eq!("all".parse::<Days>().unwrap(), "1-25".parse().unwrap());
+        assert!(!Days::ALL.contains(0) && !Days::ALL.contains(26));
+
+        assert!("0".parse::<Days>().is_err());
+        assert!("26".parse::<Days>().is_err());
+        assert!("16-10".parse::<Days>().is_err());
+        assert!("1,,2".parse::<Days>().is_err());
+    }
+}
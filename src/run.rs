@@ -0,0 +1,444 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::baseline::{self, Baseline};
+use crate::bench::{self, print_time, Benchmark};
+use crate::budget;
+use crate::cache::{self, Cache};
+use crate::input::{self, Loader, Source};
+use crate::output::{self, Format};
+use crate::registry::{self, Days, Solver, Status};
+use crate::runner::{self, skip_reason, Job, Outcome, ProfileResult, Report};
+use crate::verify::{self, Answers};
+
+// Which parts to run and how, as picked on the command line.
+pub struct Options {
+    pub days: Days,
+    pub part: Option<u8>,
+    pub include_slow: bool,
+    pub limit: Option<Duration>,
+    // Number of parts to run at the same time
+    pub jobs: usize,
+    pub format: Format,
+    // Let the parts that can read their input bit by bit do so, instead of loading it all at once
+    pub stream: bool,
+}
+
+// Runs all the selected days. Every part runs in isolation, so one that panics or hangs does not
+// stop the others. Without a cache every part runs and nothing is saved.
+pub fn run_all(loader: &Loader, mut cache: Option<&mut Cache>, options: &Options) -> Vec<Report> {
+    let mut jobs = jobs(
+        loader,
+        options.days,
+        options.part,
+        options.include_slow,
+        options.stream,
+    );
+
+    if let Some(cache) = cache.as_deref() {
+        for job in &mut jobs {
+            if let Job::Run(solver, text) = job {
+                if let Some(answer) = cache.get(solver, cache::input_hash(text)) {
+                    *job = Job::Cached(solver, answer.clone());
+                }
+            }
+        }
+    }
+
+    let mut reports = Vec::new();
+    let mut job_time = Duration::ZERO;
+    let start = Instant::now();
+
+    let is_text = options.format == Format::Text;
+
+    runner::run_jobs(&jobs, options.jobs, options.limit, |job, finished| {
+        match (job, finished) {
+            (Job::Run(solver, text), Some(finished)) => {
+                if is_text {
+                    print_outcome(solver, &finished.outcome);
+                }
+
+                let mut report =
+                    Report::new(solver.day, solver.part, &finished.outcome, finished.time);
+                if let Some(cache) = cache.as_deref_mut() {
+                    update_cache(cache, solver, text, &mut report, is_text);
+                }
+                reports.push(report);
+                job_time += finished.time;
+            }
+            // Streamed inputs are never read as a whole, so they are not hashed for the cache.
+            (Job::Stream(solver, _), Some(finished)) => {
+                if is_text {
+                    print_outcome(solver, &finished.outcome);
+                }
+
+                reports.push(Report::new(
+                    solver.day,
+                    solver.part,
+                    &finished.outcome,
+                    finished.time,
+                ));
+                job_time += finished.time;
+            }
+            (Job::Cached(solver, answer), _) => {
+                if is_text {
+                    println!(
+                        "Day {:2}, part {} = {answer} (cached)",
+                        solver.day, solver.part
+                    );
+                }
+                reports.push(Report::cached(solver, answer.clone()));
+            }
+            (Job::Skip(solver, reason), _) => {
+                if is_text {
+                    println!(
+                        "Day {:2}, part {} skipped ({reason})",
+                        solver.day, solver.part
+                    );
+                }
+                reports.push(Report::skipped(solver, reason));
+            }
+            (Job::Run(..) | Job::Stream(..), None) => {
+                unreachable!("a job that ran always has an outcome")
+            }
+        }
+    });
+
+    let wall_time = start.elapsed();
+    if let Some(cache) = cache {
+        save_cache(cache);
+    }
+
+    if !is_text {
+        output::print_answers(options.format, &reports);
+        return reports;
+    }
+
+    runner::print_summary(&reports, options.days, options.part);
+
+    // With more than one job the parts run side by side, so their summed time can be more than the
+    // time it took to run everything. Jobs that share a CPU each count the time they waited for it.
+    println!(
+        "\nWall-clock time: {}, summed job time: {} (--jobs {})",
+        bench::format_time(wall_time.as_secs_f64()).trim(),
+        bench::format_time(job_time.as_secs_f64()).trim(),
+        options.jobs
+    );
+
+    reports
+}
+
+// Runs the selected parts of a single day one after the other. Slow parts are run anyway when a
+// day is picked explicitly.
+pub fn run_day(
+    loader: &Loader,
+    mut cache: Option<&mut Cache>,
+    day: u8,
+    options: &Options,
+) -> Result<Vec<Report>, String> {
+    let solvers = registry::select(Days::single(day), options.part).collect::<Vec<_>>();
+
+    if solvers.is_empty() {
+        return Err(format!("there is no solver for day {day} yet"));
+    }
+
+    let text = Arc::<str>::from(loader.load(day).map_err(|e| e.to_string())?);
+    let is_text = options.format == Format::Text;
+
+    let mut reports = Vec::new();
+
+    for solver in solvers {
+        if let Some(reason) = skip_reason(solver, true) {
+            if is_text {
+                println!("Day {day:2}, part {} skipped ({reason})", solver.part);
+            }
+            reports.push(Report::skipped(solver, reason));
+            continue;
+        }
+
+        let input = cache::input_hash(&text);
+
+        if let Some(answer) = cache.as_deref().and_then(|cache| cache.get(solver, input)) {
+            if is_text {
+                println!("Day {day:2}, part {} = {answer} (cached)", solver.part);
+            }
+            reports.push(Report::cached(solver, answer.clone()));
+            continue;
+        }
+
+        let mut report = run(solver, &text, options.limit, options.format);
+        if let Some(cache) = cache.as_deref_mut() {
+            update_cache(cache, solver, &text, &mut report, is_text);
+        }
+        reports.push(report);
+    }
+
+    if let Some(cache) = cache {
+        save_cache(cache);
+    }
+
+    if !is_text {
+        output::print_answers(options.format, &reports);
+    }
+
+    Ok(reports)
+}
+
+// Runs every part on the inputs of every profile in `input_dir`, or checks them against `answers`.
+pub fn profiles(
+    input_dir: &Path,
+    answers: Option<&Answers>,
+    options: &Options,
+) -> Result<Vec<(String, Vec<ProfileResult>)>, String> {
+    let names = input::profiles(input_dir).map_err(|e| e.to_string())?;
+
+    let sources = std::iter::once(Source::Default).chain(names.into_iter().map(Source::Profile));
+    let mut profiles = Vec::new();
+
+    for source in sources {
+        let loader = Loader::new(input_dir.to_path_buf(), source);
+        eprintln!("Running profile `{}`", loader.id());
+
+        let results = match answers {
+            Some(answers) => {
+                let solvers = registry::select(options.days, options.part);
+                verify::verify(
+                    solvers,
+                    &loader,
+                    answers,
+                    options.include_slow,
+                    options.limit,
+                )
+                .into_iter()
+                .map(|c| ProfileResult {
+                    day: c.day,
+                    part: c.part,
+                    label: c.verdict.label(),
+                    failed: c.verdict.is_failure(),
+                })
+                .collect::<Vec<_>>()
+            }
+            None => {
+                let jobs = jobs(
+                    &loader,
+                    options.days,
+                    options.part,
+                    options.include_slow,
+                    false,
+                );
+                let mut results = Vec::new();
+
+                runner::run_jobs(&jobs, options.jobs, options.limit, |job, finished| {
+                    let report = match (job, finished) {
+                        (Job::Run(solver, _) | Job::Stream(solver, _), Some(finished)) => {
+                            Report::new(solver.day, solver.part, &finished.outcome, finished.time)
+                        }
+                        (Job::Cached(solver, answer), _) => Report::cached(solver, answer.clone()),
+                        (Job::Skip(solver, reason), _) => Report::skipped(solver, reason),
+                        (Job::Run(..) | Job::Stream(..), None) => {
+                            unreachable!("a job that ran always has an outcome")
+                        }
+                    };
+
+                    results.push(ProfileResult {
+                        day: report.day,
+                        part: report.part,
+                        label: report.label,
+                        failed: report.is_failure(),
+                    });
+                });
+
+                results
+            }
+        };
+
+        profiles.push((loader.id(), results));
+    }
+
+    runner::print_profiles(&profiles);
+
+    Ok(profiles)
+}
+
+// Benchmarks the selected parts and prints the results, checked against `budget` and compared to
+// `baseline` if they are given.
+pub fn bench(
+    loader: &Loader,
+    options: &Options,
+    settings: &bench::Settings,
+    budget: Option<Duration>,
+    baseline: Option<&Baseline>,
+) -> Vec<Benchmark> {
+    let mut benchmarks = Vec::new();
+
+    for day in registry::days().filter(|&day| options.days.contains(day)) {
+        let text = match loader.load(day) {
+            Ok(text) => Arc::<str>::from(text),
+            Err(e) => {
+                eprintln!("Skipping day {day:2}: {e}");
+                continue;
+            }
+        };
+
+        let solvers = registry::select(Days::single(day), options.part)
+            .filter(|s| match skip_reason(s, options.include_slow) {
+                Some(reason) => {
+                    eprintln!("Skipping {}: {reason}", s.name);
+                    false
+                }
+                None => true,
+            })
+            .collect::<Vec<_>>();
+
+        benchmarks.extend(bench::bench_day(
+            day,
+            &text,
+            &solvers,
+            settings,
+            options.limit,
+        ));
+    }
+
+    let total_time = benchmarks.iter().fold(0.0, |acc, x| acc + x.stats.mean);
+
+    for x in benchmarks.iter_mut() {
+        x.percentage = x.stats.mean * 100.0 / total_time;
+    }
+
+    if options.format == Format::Text {
+        Benchmark::print_header();
+        for x in &benchmarks {
+            x.print();
+        }
+
+        print!("\nTotal time taken: ");
+        print_time(total_time);
+        println!();
+    } else {
+        output::print_benchmarks(options.format, &benchmarks);
+    }
+
+    if let Some(budget) = budget {
+        let unsolved = registry::select(options.days, options.part)
+            .filter(|s| s.status == Status::Unimplemented)
+            .count();
+        budget::print_budget(&benchmarks, budget, unsolved);
+    }
+
+    if let Some(baseline) = baseline {
+        baseline::print_comparison(baseline, &benchmarks);
+    }
+
+    benchmarks
+}
+
+// Prints the answer, or what went wrong, unless the answers are printed in another format at the
+// end.
+fn run(
+    solver: &'static Solver,
+    text: &Arc<str>,
+    limit: Option<Duration>,
+    format: Format,
+) -> Report {
+    let start = Instant::now();
+    let outcome = runner::execute_with_limit(solver, text, limit);
+    let time = start.elapsed();
+
+    if format == Format::Text {
+        print_outcome(solver, &outcome);
+    }
+
+    Report::new(solver.day, solver.part, &outcome, time)
+}
+
+// A job for every solver of the given days, which skips the parts that can not run. With `stream`
+// the parts that can read their input bit by bit do so, instead of it being loaded all at once.
+fn jobs(
+    loader: &Loader,
+    days: Days,
+    part: Option<u8>,
+    include_slow: bool,
+    stream: bool,
+) -> Vec<Job> {
+    let mut jobs = Vec::new();
+
+    for day in registry::days().filter(|&day| days.contains(day)) {
+        let path = loader.path(day).filter(|_| stream);
+        // Only loaded once a part needs all of it
+        let mut text = None;
+
+        for solver in registry::select(Days::single(day), part) {
+            if let Some(reason) = skip_reason(solver, include_slow) {
+                jobs.push(Job::Skip(solver, reason.to_string()));
+                continue;
+            }
+
+            if let (Some(path), Some(_)) = (&path, solver.stream) {
+                jobs.push(Job::Stream(solver, path.clone()));
+                continue;
+            }
+
+            let text = text.get_or_insert_with(|| loader.load(day).map(Arc::<str>::from));
+
+            match text {
+                Ok(text) => jobs.push(Job::Run(solver, Arc::clone(text))),
+                Err(e) => jobs.push(Job::Skip(solver, e.to_string())),
+            }
+        }
+    }
+
+    jobs
+}
+
+pub fn print_outcome(solver: &Solver, outcome: &Outcome) {
+    let (day, part) = (solver.day, solver.part);
+
+    match outcome {
+        Outcome::Answer(answer) => println!("Day {day:2}, part {part} = {answer}"),
+        Outcome::Error(e) => {
+            println!("Day {day:2}, part {part} failed");
+            eprintln!("error: {e}");
+        }
+        Outcome::Panic(message) => {
+            println!("Day {day:2}, part {part} panicked");
+            eprintln!("error: {message}");
+        }
+        Outcome::Unimplemented(message) => {
+            println!("Day {day:2}, part {part} is not implemented yet ({message})")
+        }
+        Outcome::Timeout(limit) => println!("Day {day:2}, part {part} TIMEOUT after {limit:?}"),
+    }
+}
+
+// Saves a fresh answer in the cache. If the cache had another answer for the same input, the report
+// becomes a regression.
+fn update_cache(
+    cache: &mut Cache,
+    solver: &Solver,
+    text: &str,
+    report: &mut Report,
+    is_text: bool,
+) {
+    let Some(answer) = &report.answer else {
+        return;
+    };
+
+    if let Some(previous) = cache.insert(solver, cache::input_hash(text), answer) {
+        if is_text {
+            println!(
+                "Day {:2}, part {} REGRESSION, the answer was {previous} before",
+                solver.day, solver.part
+            );
+        }
+
+        report.label = "REGRESSION";
+        report.detail = format!("the answer was {previous} before");
+    }
+}
+
+fn save_cache(cache: &mut Cache) {
+    if let Err(e) = cache.save() {
+        eprintln!("warning: {e}");
+    }
+}
@@ -0,0 +1,521 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::error::SolveError;
+use crate::registry::{self, Days, Solver, Status};
+
+// What came out of running a solver once.
+pub enum Outcome {
+    Answer(Answer),
+    Error(SolveError),
+    Panic(String),
+    // Hit a `todo!()` or `unimplemented!()`
+    Unimplemented(String),
+    // Gave up waiting for an answer after this long
+    Timeout(Duration),
+}
+
+// Solvers are isolated from each other with `catch_unwind`, which does nothing when panics abort.
+#[cfg(panic = "abort")]
+compile_error!("the runner needs panics to unwind, build with `panic = \"unwind\"`");
+
+// Solver threads get the same stack size as the main thread has on Linux, since some solvers
+// recurse deeply.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+// Runs `solver` on `text`, catching a panic instead of letting it take down the whole run. This
+// only works when panics unwind, which is why the release profile does not abort on panic.
+pub fn execute(solver: &Solver, text: &str) -> Outcome {
+    outcome(catch_panic(|| (solver.run)(text)))
+}
+
+// Like `execute`, but reads the input from the file at `path` as the solver goes, if it can. Other
+// solvers get the whole file at once.
+pub fn execute_streamed(solver: &Solver, path: &Path) -> Outcome {
+    let read_error = |e: io::Error| {
+        Outcome::Error(SolveError::new(
+            "",
+            format!("could not read {}: {e}", path.display()),
+        ))
+    };
+
+    let Some(stream) = solver.stream else {
+        return match std::fs::read_to_string(path) {
+            Ok(text) => execute(solver, &text),
+            Err(e) => read_error(e),
+        };
+    };
+
+    match File::open(path) {
+        Ok(file) => outcome(catch_panic(|| stream(&mut BufReader::new(file)))),
+        Err(e) => read_error(e),
+    }
+}
+
+fn outcome(result: Result<Result<Answer, SolveError>, String>) -> Outcome {
+    match result {
+        Ok(Ok(answer)) => Outcome::Answer(answer),
+        Ok(Err(e)) => Outcome::Error(e),
+        // The messages of `todo!()` and `unimplemented!()`
+        Err(message)
+            if message.starts_with("not yet implemented")
+                || message.starts_with("not implemented") =>
+        {
+            Outcome::Unimplemented(message)
+        }
+        Err(message) => Outcome::Panic(message),
+    }
+}
+
+thread_local! {
+    // Whether `catch_panic` is running on this thread, so the panic hook knows to stay quiet.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    // Where the last panic caught by `catch_panic` happened.
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Replaces the default panic hook with one that keeps quiet about the panics that `catch_panic`
+// catches, since the runner reports those itself. Any other panic is printed as usual.
+pub fn install_panic_hook() {
+    let default = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.get() {
+            LOCATION.set(info.location().map(|l| l.to_string()));
+        } else {
+            default(info);
+        }
+    }));
+}
+
+// Calls `f`, turning a panic into an error with the panic message and where it happened.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.map_err(|payload| {
+        let message = panic_message(payload.as_ref());
+
+        match LOCATION.take() {
+            Some(location) => format!("{message} at {location}"),
+            None => message,
+        }
+    })
+}
+
+// Like `execute`, but gives up when `solver` does not finish within `limit`.
+pub fn execute_with_limit(
+    solver: &'static Solver,
+    text: &Arc<str>,
+    limit: Option<Duration>,
+) -> Outcome {
+    let text = Arc::clone(text);
+
+    match with_limit(limit, move || execute(solver, &text)) {
+        Some(outcome) => outcome,
+        None => Outcome::Timeout(limit.unwrap_or_default()),
+    }
+}
+
+// Like `execute_streamed`, but gives up when `solver` does not finish within `limit`.
+pub fn execute_streamed_with_limit(
+    solver: &'static Solver,
+    path: &Path,
+    limit: Option<Duration>,
+) -> Outcome {
+    let path = path.to_path_buf();
+
+    match with_limit(limit, move || execute_streamed(solver, &path)) {
+        Some(outcome) => outcome,
+        None => Outcome::Timeout(limit.unwrap_or_default()),
+    }
+}
+
+// Runs `f` on a worker thread and waits at most `limit` for it to finish, returning `None` if it
+// did not. There is no way to stop a thread, so one that runs over keeps going in the background
+// until the process exits. Without a limit `f` is simply called on the current thread.
+pub fn with_limit<T: Send + 'static>(
+    limit: Option<Duration>,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let Some(limit) = limit else {
+        return Some(f());
+    };
+
+    let (tx, rx) = mpsc::channel();
+
+    thread::Builder::new()
+        .name("solver".to_string())
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            // The receiver is gone if we stopped waiting, then nobody cares about the result.
+            let _ = tx.send(f());
+        })
+        .expect("failed to spawn a solver thread");
+
+    match rx.recv_timeout(limit) {
+        Ok(x) => Some(x),
+        Err(RecvTimeoutError::Timeout) => None,
+        Err(RecvTimeoutError::Disconnected) => panic!("solver thread exited without a result"),
+    }
+}
+
+// A part to run when running everything, its answer from the cache or the reason it is skipped.
+// Inputs that are too big to keep in memory are streamed from their file.
+pub enum Job {
+    Run(&'static Solver, Arc<str>),
+    Stream(&'static Solver, PathBuf),
+    Cached(&'static Solver, Answer),
+    Skip(&'static Solver, String),
+}
+
+// How long a worker waited for the outcome of a job. This is wall-clock time, not CPU time: it
+// includes the time the solver shared the CPU with other jobs, and the whole limit if it timed out.
+pub struct Finished {
+    pub outcome: Outcome,
+    pub time: Duration,
+}
+
+// Runs the jobs on `threads` worker threads. `report` is called for every job in order, as soon as
+// it and all the jobs before it are done, so the order does not depend on the number of threads.
+// It gets `None` for cached and skipped jobs.
+pub fn run_jobs(
+    jobs: &[Job],
+    threads: usize,
+    limit: Option<Duration>,
+    mut report: impl FnMut(&Job, Option<Finished>),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            let (next, tx) = (&next, tx.clone());
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i) else {
+                    break;
+                };
+
+                let start = Instant::now();
+                let outcome = match job {
+                    Job::Run(solver, text) => Some(execute_with_limit(solver, text, limit)),
+                    Job::Stream(solver, path) => {
+                        Some(execute_streamed_with_limit(solver, path, limit))
+                    }
+                    Job::Cached(..) | Job::Skip(..) => None,
+                };
+                let finished = outcome.map(|outcome| Finished {
+                    outcome,
+                    time: start.elapsed(),
+                });
+
+                if tx.send((i, finished)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // Only the workers can send from here on, so the loop below ends when they are done.
+        drop(tx);
+
+        let mut done = jobs.iter().map(|_| None).collect::<Vec<_>>();
+        let mut reported = 0;
+
+        for (i, finished) in rx {
+            done[i] = Some(finished);
+
+            while let Some(finished) = done.get_mut(reported).and_then(Option::take) {
+                report(&jobs[reported], finished);
+                reported += 1;
+            }
+        }
+    });
+}
+
+// Returns why `solver` should not be run, if it should be skipped.
+pub fn skip_reason(solver: &Solver, include_slow: bool) -> Option<&'static str> {
+    match solver.status {
+        Status::Ok => None,
+        Status::Slow if include_slow => None,
+        Status::Slow => Some("slow, pass --slow to include it"),
+        Status::Unimplemented => Some("not implemented"),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panicked without a message".to_string()
+    }
+}
+
+// How a day and part ended up, for the summary at the end and the machine-readable output.
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub label: &'static str,
+    pub detail: String,
+    pub answer: Option<Answer>,
+    // How long the solver ran, `None` if it did not run
+    pub time: Option<Duration>,
+}
+
+impl Report {
+    pub fn new(day: u8, part: u8, outcome: &Outcome, time: Duration) -> Report {
+        let (label, detail) = match outcome {
+            Outcome::Answer(_) => ("OK", String::new()),
+            Outcome::Error(e) => ("ERROR", e.message.clone()),
+            Outcome::Panic(message) => ("PANIC", message.clone()),
+            Outcome::Unimplemented(message) => ("UNIMPLEMENTED", message.clone()),
+            Outcome::Timeout(limit) => ("TIMEOUT", format!("no answer after {limit:?}")),
+        };
+
+        let answer = match outcome {
+            Outcome::Answer(answer) => Some(answer.clone()),
+            _ => None,
+        };
+
+        Report {
+            day,
+            part,
+            label,
+            detail,
+            answer,
+            time: Some(time),
+        }
+    }
+
+    pub fn cached(solver: &Solver, answer: Answer) -> Report {
+        Report {
+            day: solver.day,
+            part: solver.part,
+            label: "OK",
+            detail: "cached".to_string(),
+            answer: Some(answer),
+            time: None,
+        }
+    }
+
+    pub fn skipped(solver: &Solver, reason: &str) -> Report {
+        let label = match solver.status {
+            Status::Unimplemented => "UNIMPLEMENTED",
+            _ => "SKIPPED",
+        };
+
+        Report {
+            day: solver.day,
+            part: solver.part,
+            label,
+            detail: reason.to_string(),
+            answer: None,
+            time: None,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self.label, "ERROR" | "PANIC" | "TIMEOUT" | "REGRESSION")
+    }
+}
+
+// Prints a line for every selected day and part, including the ones that do not have a solver yet.
+pub fn print_summary(reports: &[Report], days: Days, part: Option<u8>) {
+    let missing = (1..=25)
+        .filter(|&day| days.contains(day))
+        .flat_map(|day| [(day, 1), (day, 2)])
+        .filter(|&(_, p)| part.is_none_or(|part| p == part))
+        .filter(|&(day, part)| {
+            !registry::SOLVERS
+                .iter()
+                .any(|s| (s.day, s.part) == (day, part))
+        })
+        .map(|(day, part)| Report {
+            day,
+            part,
+            label: "UNIMPLEMENTED",
+            detail: "no solver".to_string(),
+            answer: None,
+            time: None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut all = reports.iter().chain(&missing).collect::<Vec<_>>();
+    all.sort_by_key(|r| (r.day, r.part));
+
+    println!("\nSummary:");
+
+    for r in &all {
+        match r.detail.as_str() {
+            "" => println!("Day {:2}, part {} | {}", r.day, r.part, r.label),
+            detail => println!(
+                "Day {:2}, part {} | {:13} | {detail}",
+                r.day, r.part, r.label
+            ),
+        }
+    }
+
+    let count = |label| all.iter().filter(|r| r.label == label).count();
+
+    println!(
+        "\n{} ok, {} regressed, {} errors, {} panicked, {} unimplemented, {} timed out, {} skipped",
+        count("OK"),
+        count("REGRESSION"),
+        count("ERROR"),
+        count("PANIC"),
+        count("UNIMPLEMENTED"),
+        count("TIMEOUT"),
+        count("SKIPPED"),
+    );
+
+    if count("TIMEOUT") > 0 {
+        eprintln!(
+            "note: parts that timed out can not be stopped, they keep running in the background \
+             until the program exits"
+        );
+    }
+}
+
+// How a day and part ended up on the inputs of one profile, either a `Report` label or a verdict.
+pub struct ProfileResult {
+    pub day: u8,
+    pub part: u8,
+    pub label: &'static str,
+    pub failed: bool,
+}
+
+// Prints a table with a column per profile, followed by the parts that failed on each profile.
+pub fn print_profiles(profiles: &[(String, Vec<ProfileResult>)]) {
+    let mut keys = profiles
+        .iter()
+        .flat_map(|(_, results)| results.iter().map(|r| (r.day, r.part)))
+        .collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+
+    let width = |name: &str| name.len().max(13);
+
+    print!("Day | Part");
+    for (name, _) in profiles {
+        print!(" | {name:w$}", w = width(name));
+    }
+    println!();
+
+    for (day, part) in keys {
+        print!("{day:3} | {part:4}");
+
+        for (name, results) in profiles {
+            let label = results
+                .iter()
+                .find(|r| (r.day, r.part) == (day, part))
+                .map_or("", |r| r.label);
+            print!(" | {label:w$}", w = width(name));
+        }
+
+        println!();
+    }
+
+    println!();
+
+    for (name, results) in profiles {
+        let failed = results
+            .iter()
+            .filter(|r| r.failed)
+            .map(|r| format!("day {} part {}", r.day, r.part))
+            .collect::<Vec<_>>();
+
+        match failed.len() {
+            0 => println!("{name}: no failures"),
+            n => println!("{name}: {n} failed ({})", failed.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{execute, run_jobs, Job, Outcome};
+    use crate::answer::Answer;
+    use crate::error::SolveError;
+    use crate::registry::{Solver, Status};
+    use std::sync::Arc;
+
+    fn solver(run: fn(&str) -> Result<Answer, SolveError>) -> Solver {
+        Solver {
+            day: 1,
+            part: 1,
+            name: "test",
+            status: Status::Ok,
+            run,
+            phases: None,
+            stream: None,
+            source: "",
+        }
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        match execute(&solver(|_| panic!("oops")), "") {
+            Outcome::Panic(message) => {
+                assert!(message.starts_with("oops"), "{message}")
+            }
+            _ => panic!("expected a panic"),
+        }
+
+        assert!(matches!(
+            execute(&solver(|_| todo!()), ""),
+            Outcome::Unimplemented(_)
+        ));
+        assert!(matches!(
+            execute(&solver(|input| Ok(input.len().into())), "abc"),
+            Outcome::Answer(_)
+        ));
+    }
+
+    #[test]
+    fn jobs_are_reported_in_order() {
+        static SOLVER: Solver = Solver {
+            day: 1,
+            part: 1,
+            name: "test",
+            status: Status::Ok,
+            run: |input| Ok(input.len().into()),
+            phases: None,
+            stream: None,
+            source: "",
+        };
+
+        let jobs = (0..20)
+            .map(|i| match i % 5 {
+                0 => Job::Skip(&SOLVER, i.to_string()),
+                _ => Job::Run(&SOLVER, Arc::from("x".repeat(i))),
+            })
+            .collect::<Vec<_>>();
+
+        let mut seen = Vec::new();
+
+        run_jobs(&jobs, 4, None, |job, finished| match (job, finished) {
+            (Job::Skip(_, reason), None) => seen.push(reason.parse::<usize>().unwrap()),
+            (Job::Run(..), Some(finished)) => match finished.outcome {
+                Outcome::Answer(answer) => seen.push(answer.to_string().parse().unwrap()),
+                _ => panic!("expected an answer"),
+            },
+            _ => panic!("only skipped jobs have no outcome"),
+        });
+
+        assert_eq!(seen, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input;
+
+// Lines in the source are kept within this many characters, like rustfmt does.
+const MAX_WIDTH: usize = 100;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    AlreadyExists(PathBuf),
+    Io { path: PathBuf, source: io::Error },
+    // The file does not look like expected, so it is left alone
+    Unrecognized { path: PathBuf, what: &'static str },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::AlreadyExists(path) => {
+                write!(f, "{} already exists", path.display())
+            }
+            ScaffoldError::Io { path, source } => {
+                write!(f, "could not access {}: {source}", path.display())
+            }
+            ScaffoldError::Unrecognized { path, what } => {
+                write!(f, "could not find {what} in {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScaffoldError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    std::fs::read_to_string(path).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, text: &str) -> Result<(), ScaffoldError> {
+    std::fs::write(path, text).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn module(day: u8) -> String {
+    format!("day{day:02}")
+}
+
+fn skeleton(day: u8) -> String {
+    let example = input::example_file_name(day, 1);
+
+    format!(
+        r#"use crate::error::SolveError;
+
+pub fn part1(_input: &str) -> Result<u64, SolveError> {{
+    todo!()
+}}
+
+pub fn part2(_input: &str) -> Result<u64, SolveError> {{
+    todo!()
+}}
+
+#[cfg(test)]
+mod tests {{
+    const EXAMPLE: &str = include_str!("../input/examples/{example}");
+
+    #[test]
+    #[ignore = "not implemented yet"]
+    fn part1() {{
+        assert_eq!(super::part1(EXAMPLE), Ok(0));
+    }}
+
+    #[test]
+    #[ignore = "not implemented yet"]
+    fn part2() {{
+        assert_eq!(super::part2(EXAMPLE), Ok(0));
+    }}
+}}
+"#
+    )
+}
+
+// The day of a `pub mod dayNN;` line.
+fn module_day(line: &str) -> Option<u8> {
+    line.strip_prefix("pub mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+// Adds `pub mod dayNN;` to the modules of the other days, keeping them ordered.
+fn add_module(lib: &str, day: u8) -> Option<String> {
+    let lines = lib.lines().collect::<Vec<_>>();
+    let last = lines.iter().rposition(|line| module_day(line).is_some())?;
+    let at = lines
+        .iter()
+        .position(|line| module_day(line).is_some_and(|d| d > day))
+        .unwrap_or(last + 1);
+
+    let new = format!("pub mod {};", module(day));
+    let mut out = lines[..at].to_vec();
+    out.push(&new);
+    out.extend(&lines[at..]);
+
+    Some(out.join("\n") + "\n")
+}
+
+// The day of a `solver!(N, ...)` line.
+fn solver_day(line: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix("solver!(")?
+        .split_once(',')?
+        .0
+        .parse()
+        .ok()
+}
+
+// Imports the module of the day in the registry and adds solvers for both of its parts, which are
+// marked as unimplemented.
+fn register(registry: &str, day: u8) -> Option<String> {
+    // The modules of all days are imported in a single `use crate::{...};`.
+    let start = registry.find("use crate::{")?;
+    let len = registry[start..].find("};")? + 2;
+    let names = &registry[start + "use crate::{".len()..start + len - 2];
+
+    let mut names = names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    let new = module(day);
+    names.push(&new);
+    names.sort();
+
+    let single = format!("use crate::{{{}}};", names.join(", "));
+    let import = if single.len() <= MAX_WIDTH {
+        single
+    } else {
+        let mut lines = vec![String::from("   ")];
+
+        for name in names {
+            let line = lines.last_mut().unwrap();
+
+            if line.len() + 1 + name.len() + 1 > MAX_WIDTH {
+                lines.push(format!("    {name},"));
+            } else {
+                *line += &format!(" {name},");
+            }
+        }
+
+        format!("use crate::{{\n{}\n}};", lines.join("\n"))
+    };
+
+    let registry = format!("{}{import}{}", &registry[..start], &registry[start + len..]);
+
+    let lines = registry.lines().collect::<Vec<_>>();
+    let last = lines.iter().rposition(|line| solver_day(line).is_some())?;
+    let at = lines
+        .iter()
+        .position(|line| solver_day(line).is_some_and(|d| d > day))
+        .unwrap_or(last + 1);
+
+    let solvers =
+        [1, 2].map(|part| format!("    solver!({day}, {part}, {new}::part{part}, Unimplemented),"));
+    let mut out = lines[..at].to_vec();
+    out.extend(solvers.iter().map(String::as_str));
+    out.extend(&lines[at..]);
+
+    Some(out.join("\n") + "\n")
+}
+
+// Creates `src/dayNN.rs` with stubs for both parts, placeholders for its input and its first
+// example and registers the day, so it is picked up by running, benchmarking and verifying.
+// Returns the files that were created or changed.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let src = root.join("src");
+    let source = src.join(format!("{}.rs", module(day)));
+
+    if source.exists() {
+        return Err(ScaffoldError::AlreadyExists(source));
+    }
+
+    // Everything is checked before anything is written, so a failure leaves no half-added day.
+    let lib_path = src.join("lib.rs");
+    let lib = add_module(&read(&lib_path)?, day).ok_or(ScaffoldError::Unrecognized {
+        path: lib_path.clone(),
+        what: "the `pub mod dayNN;` lines",
+    })?;
+
+    let registry_path = src.join("registry.rs");
+    let registry = register(&read(&registry_path)?, day).ok_or(ScaffoldError::Unrecognized {
+        path: registry_path.clone(),
+        what: "the imports of the days and the solvers",
+    })?;
+
+    let input_dir = root.join(input::DEFAULT_DIR);
+    let placeholders = [
+        input_dir.join(input::file_name(day)),
+        input_dir
+            .join(input::EXAMPLES_DIR)
+            .join(input::example_file_name(day, 1)),
+    ];
+
+    write(&source, &skeleton(day))?;
+    write(&lib_path, &lib)?;
+    write(&registry_path, &registry)?;
+
+    let mut changed = vec![source, lib_path, registry_path];
+
+    for path in placeholders {
+        if path.exists() {
+            continue;
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|source| ScaffoldError::Io {
+                path: dir.to_path_buf(),
+                source,
+            })?;
+        }
+
+        write(&path, "")?;
+        changed.push(path);
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_module, register};
+
+    #[test]
+    fn modules_stay_ordered() {
+        let lib = "pub mod util;\n\npub mod day01;\npub mod day03;\n\nuse foo;\n";
+
+        assert_eq!(
+            add_module(lib, 2).unwrap(),
+            "pub mod util;\n\npub mod day01;\npub mod day02;\npub mod day03;\n\nuse foo;\n"
+        );
+        assert_eq!(
+            add_module(lib, 25).unwrap(),
+            "pub mod util;\n\npub mod day01;\npub mod day03;\npub mod day25;\n\nuse foo;\n"
+        );
+        assert_eq!(add_module("pub mod util;\n", 1), None);
+    }
+
+    #[test]
+    fn days_are_registered() {
+        let registry = "use crate::{day01, day03};\n\npub static SOLVERS: &[Solver] = &[\n    solver!(1, 1, day01::part1),\n    solver!(3, 1, day03::parse => part1),\n];\n";
+
+        assert_eq!(
+            register(registry, 2).unwrap(),
+            "use crate::{day01, day02, day03};\n\npub static SOLVERS: &[Solver] = &[\n    solver!(1, 1, day01::part1),\n    solver!(2, 1, day02::part1, Unimplemented),\n    solver!(2, 2, day02::part2, Unimplemented),\n    solver!(3, 1, day03::parse => part1),\n];\n"
+        );
+
+        // Long imports are wrapped like rustfmt does
+        let days = (1..=24).map(|d| format!("day{d:02}")).collect::<Vec<_>>();
+        let registry = format!(
+            "use crate::{{{}}};\nsolver!(1, 1, day01::part1),\n",
+            days.join(", ")
+        );
+        let registered = register(&registry, 25).unwrap();
+
+        assert!(registered.starts_with("use crate::{\n    day01, "));
+        assert!(registered.contains(" day13,\n    day14, "));
+        assert!(registered.contains("day24, day25,\n};\n"));
+        assert!(registered.lines().all(|line| line.len() <= 100));
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::SolveError;
+use crate::input::{self, InputError};
+
+#[derive(Debug)]
+pub enum ScaleError {
+    // There is no way to scale up the input of this day
+    Unsupported(u8),
+    Input(InputError),
+    Invalid(SolveError),
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for ScaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaleError::Unsupported(day) => {
+                write!(f, "the input of day {day} can not be scaled up")
+            }
+            ScaleError::Input(e) => e.fmt(f),
+            ScaleError::Invalid(e) => write!(f, "could not scale up the input: {e}"),
+            ScaleError::Io { path, source } => {
+                write!(f, "could not write {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScaleError::Input(e) => Some(e),
+            ScaleError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// The input repeated `factor` times, for the days where every line stands on its own.
+fn repeat(text: &str, factor: usize) -> String {
+    let mut out = String::with_capacity((text.len() + 1) * factor);
+
+    for _ in 0..factor {
+        for line in text.lines() {
+            out += line;
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+// Like `repeat`, but the lines are numbered like `Card 1: ...` and keep counting up in the copies.
+fn renumber(text: &str, factor: usize, name: &str) -> Result<String, SolveError> {
+    let mut out = String::with_capacity((text.len() + 1) * factor);
+    let mut id = 0;
+
+    for _ in 0..factor {
+        for line in text.lines() {
+            let Some((_, rest)) = line.split_once(':') else {
+                return Err(SolveError::missing_after(
+                    line,
+                    format!("expected `:` after the {} id", name.to_lowercase()),
+                ));
+            };
+
+            id += 1;
+            out += &format!("{name} {id}:{rest}\n");
+        }
+    }
+
+    Ok(out)
+}
+
+// Day 3 is tiled `factor` by `factor` times. A column and a row of `.` between the tiles keep
+// numbers from running into each other and symbols from touching numbers of the next tile, so the
+// answers are `factor * factor` times the answers of the input.
+fn tile(text: &str, factor: usize) -> String {
+    let width = text.lines().map(str::len).max().unwrap_or(0);
+    let empty = ".".repeat((width + 1) * factor);
+    let mut out = String::new();
+
+    for _ in 0..factor {
+        for line in text.lines() {
+            for _ in 0..factor {
+                out += &format!("{line:.<width$}.");
+            }
+            out.push('\n');
+        }
+
+        out += &empty;
+        out.push('\n');
+    }
+
+    out
+}
+
+// Whether the tile connects to its right and to the tile below it.
+fn connections(tile: u8) -> (bool, bool) {
+    (
+        matches!(tile, b'-' | b'L' | b'F'),
+        matches!(tile, b'|' | b'7' | b'F'),
+    )
+}
+
+// Day 10 gets every tile replaced by a block of `factor` by `factor` tiles, with the original tile
+// in the top left corner and pipes to the blocks it connects to. The loop becomes `factor` times as
+// long, so the answer of part 1 is `factor` times the answer of the input.
+fn upscale(text: &str, factor: usize) -> Result<String, SolveError> {
+    let rows = text.lines().map(str::as_bytes).collect::<Vec<_>>();
+
+    let connects = |y: usize, x: usize| -> (bool, bool) {
+        let tile = rows[y][x];
+        if tile != b'S' {
+            return connections(tile);
+        }
+
+        // The start tile connects to the pipes that connect to it
+        let right = rows[y]
+            .get(x + 1)
+            .is_some_and(|&t| matches!(t, b'-' | b'J' | b'7'));
+        let down = rows
+            .get(y + 1)
+            .and_then(|row| row.get(x))
+            .is_some_and(|&t| matches!(t, b'|' | b'L' | b'J'));
+
+        (right, down)
+    };
+
+    if !rows.iter().flat_map(|row| row.iter()).any(|&t| t == b'S') {
+        return Err(SolveError::new(text, "there is no start tile `S`"));
+    }
+
+    let mut out = String::new();
+
+    for (y, row) in rows.iter().enumerate() {
+        for i in 0..factor {
+            for (x, &tile) in row.iter().enumerate() {
+                let (right, down) = connects(y, x);
+
+                for j in 0..factor {
+                    let c = match (i, j) {
+                        (0, 0) => tile as char,
+                        (0, _) if right => '-',
+                        (_, 0) if down => '|',
+                        _ => '.',
+                    };
+                    out.push(c);
+                }
+            }
+            out.push('\n');
+        }
+    }
+
+    Ok(out)
+}
+
+// The input of `day` scaled up by `factor`.
+fn scale_text(day: u8, text: &str, factor: usize) -> Result<String, ScaleError> {
+    let scaled = match day {
+        1 | 9 | 12 => Ok(repeat(text, factor)),
+        2 => renumber(text, factor, "Game"),
+        3 => Ok(tile(text, factor)),
+        4 => renumber(text, factor, "Card"),
+        10 => upscale(text, factor),
+        _ => return Err(ScaleError::Unsupported(day)),
+    };
+
+    scaled.map_err(ScaleError::Invalid)
+}
+
+// Writes the input of `day` scaled up by `factor` to `bigboy/day_XX.txt` in the input directory,
+// which is where `--bigboy` reads it from. Returns the path of the file.
+pub fn scale(input_dir: &Path, day: u8, factor: usize) -> Result<PathBuf, ScaleError> {
+    let loader = input::Loader::new(input_dir.to_path_buf(), input::Source::Default);
+    let text = loader.load(day).map_err(ScaleError::Input)?;
+    let scaled = scale_text(day, &text, factor)?;
+
+    let dir = input_dir.join(input::BIGBOY);
+    let path = dir.join(input::file_name(day));
+
+    std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(&path, scaled))
+        .map_err(|source| ScaleError::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scale_text;
+    use crate::{day03, day10};
+
+    #[test]
+    fn inputs_are_scaled() {
+        assert_eq!(
+            scale_text(1, "1abc2\na1b2", 2).unwrap(),
+            "1abc2\na1b2\n1abc2\na1b2\n"
+        );
+        assert_eq!(
+            scale_text(4, "Card   1: 1 | 1\nCard   2: 2 | 3\n", 2).unwrap(),
+            "Card 1: 1 | 1\nCard 2: 2 | 3\nCard 3: 1 | 1\nCard 4: 2 | 3\n"
+        );
+        assert!(scale_text(5, "seeds: 1", 2).is_err());
+
+        let example = include_str!("../input/examples/day_03_1.txt");
+        let schematic = day03::parse(&scale_text(3, example, 3).unwrap()).unwrap();
+        assert_eq!(day03::part1(&schematic), Ok(9 * 4361));
+        assert_eq!(day03::part2(&schematic), Ok(9 * 467835));
+
+        let example = include_str!("../input/examples/day_10_3.txt");
+        assert_eq!(
+            day10::part1(&scale_text(10, example, 3).unwrap()),
+            day10::part1(example).map(|steps| 3 * steps)
+        );
+        assert_eq!(
+            scale_text(10, ".....\n.S-7.\n.|.|.\n.L-J.\n.....\n", 2).unwrap(),
+            "..........\n..........\n..S---7...\n..|...|...\n..|...|...\n..|...|...\n..L---J...\n..........\n..........\n..........\n"
+        );
+    }
+}
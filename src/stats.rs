@@ -0,0 +1,137 @@
+use crate::util::Rng;
+
+// Number of resamples used for the bootstrap confidence interval.
+const RESAMPLES: usize = 1000;
+
+// Samples that are further than this many (scaled) median absolute deviations from the median are
+// treated as outliers.
+const MAD_LIMIT: f64 = 3.0;
+
+// Makes the MAD comparable to the standard deviation of a normal distribution.
+const MAD_SCALE: f64 = 1.4826;
+
+// Fixed so that the same samples always give the same confidence interval.
+const SEED: u64 = 0x00c0ffee;
+
+// Statistics of a set of timings, all in seconds. The mean, standard deviation and confidence
+// interval are computed without the outliers, the order statistics use every sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub mean: f64,
+    pub stddev: f64,
+    pub median: f64,
+    pub min: f64,
+    pub p95: f64,
+    // 95% bootstrap confidence interval of the mean
+    pub ci: (f64, f64),
+    pub outliers: usize,
+    pub samples: Vec<f64>,
+}
+
+pub fn summarize(samples: Vec<f64>) -> Summary {
+    assert!(!samples.is_empty(), "can not summarize zero samples");
+
+    let mut sorted = samples.clone();
+    sorted.sort_by(f64::total_cmp);
+
+    let median = percentile(&sorted, 0.5);
+
+    let mut deviations = sorted
+        .iter()
+        .map(|x| (x - median).abs())
+        .collect::<Vec<_>>();
+    deviations.sort_by(f64::total_cmp);
+    let mad = MAD_SCALE * percentile(&deviations, 0.5);
+
+    // With a MAD of zero at least half of the samples are identical and everything else would be
+    // an outlier, so nothing is filtered then.
+    let kept = sorted
+        .iter()
+        .copied()
+        .filter(|x| mad == 0.0 || (x - median).abs() <= MAD_LIMIT * mad)
+        .collect::<Vec<_>>();
+
+    let mean = mean(&kept);
+    let stddev = {
+        let s = kept.iter().map(|x| (x - mean).powf(2.0)).sum::<f64>();
+        (s / kept.len() as f64).sqrt()
+    };
+
+    Summary {
+        mean,
+        stddev,
+        median,
+        min: sorted[0],
+        p95: percentile(&sorted, 0.95),
+        ci: bootstrap(&kept),
+        outliers: sorted.len() - kept.len(),
+        samples,
+    }
+}
+
+fn mean(xs: &[f64]) -> f64 {
+    xs.iter().sum::<f64>() / xs.len() as f64
+}
+
+// Nearest-rank percentile of sorted samples, `p` is in `0.0..=1.0`.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn bootstrap(xs: &[f64]) -> (f64, f64) {
+    let mut rng = Rng::new(SEED);
+    let mut means = Vec::with_capacity(RESAMPLES);
+
+    for _ in 0..RESAMPLES {
+        let sum = (0..xs.len())
+            .map(|_| xs[rng.below(xs.len() as u64) as usize])
+            .sum::<f64>();
+        means.push(sum / xs.len() as f64);
+    }
+
+    means.sort_by(f64::total_cmp);
+
+    (percentile(&means, 0.025), percentile(&means, 0.975))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::summarize;
+
+    #[test]
+    fn order_statistics() {
+        let s = summarize((1..=20).rev().map(f64::from).collect());
+
+        assert_eq!(s.min, 1.0);
+        assert_eq!(s.median, 10.0);
+        assert_eq!(s.p95, 19.0);
+        assert_eq!(s.mean, 10.5);
+        assert_eq!(s.outliers, 0);
+        assert_eq!(s.samples[0], 20.0);
+    }
+
+    #[test]
+    fn outliers_do_not_affect_the_mean() {
+        let mut samples = vec![10.0, 11.0, 9.0, 10.0, 10.5, 9.5, 10.0, 10.0];
+        let s = summarize(samples.clone());
+
+        samples.push(1000.0);
+        let t = summarize(samples);
+
+        assert_eq!(t.outliers, 1);
+        assert_eq!(t.mean, s.mean);
+        assert_eq!(t.stddev, s.stddev);
+        assert_eq!(t.p95, 1000.0);
+    }
+
+    #[test]
+    fn confidence_interval_contains_the_mean() {
+        let samples = (0..50).map(|i| 100.0 + (i % 7) as f64).collect::<Vec<_>>();
+        let s = summarize(samples.clone());
+
+        assert!(s.ci.0 <= s.mean && s.mean <= s.ci.1);
+        assert!(s.ci.0 < s.ci.1);
+        assert_eq!(summarize(samples).ci, s.ci);
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::aoc::{Hint, Reply};
+use crate::util::{escape_field, unescape_field};
+
+// Every answer that was sent to the site and what it said about it, so the same answer is never
+// sent twice. It is a plain text file with a line per submission:
+//
+//     <unix time><TAB><day><TAB><part><TAB><answer><TAB><verdict><TAB><unix time to wait until>
+//
+// Backslashes, tabs and line breaks in the answer are escaped with a backslash.
+pub struct Log {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+pub struct Submission {
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: String,
+    // No answer should be sent before this time
+    pub until: u64,
+}
+
+#[derive(Debug)]
+pub enum LogError {
+    Io { path: PathBuf, source: io::Error },
+    Parse { path: PathBuf, line: usize },
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogError::Io { path, source } => {
+                write!(f, "could not access {}: {source}", path.display())
+            }
+            LogError::Parse { path, line } => {
+                write!(f, "{}:{line}: not a valid submission", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LogError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LogError::Io { source, .. } => Some(source),
+            LogError::Parse { .. } => None,
+        }
+    }
+}
+
+pub fn verdict(reply: &Reply) -> &'static str {
+    match reply {
+        Reply::Correct => "correct",
+        Reply::Wrong {
+            hint: Some(Hint::TooHigh),
+            ..
+        } => "too-high",
+        Reply::Wrong {
+            hint: Some(Hint::TooLow),
+            ..
+        } => "too-low",
+        Reply::Wrong { hint: None, .. } => "wrong",
+        Reply::TooSoon { .. } => "too-soon",
+        Reply::WrongLevel => "wrong-level",
+        Reply::Unknown(_) => "unknown",
+    }
+}
+
+impl Log {
+    // A log that does not exist yet is empty.
+    pub fn load(path: &Path) -> Result<Log, LogError> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => {
+                return Err(LogError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+
+        let submissions = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                parse_line(line).ok_or(LogError::Parse {
+                    path: path.to_path_buf(),
+                    line: i + 1,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Log {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    // Why `answer` should not be sent at `now`, if there is a reason.
+    pub fn refusal(&self, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+        let mut previous = self
+            .submissions
+            .iter()
+            .filter(|s| (s.day, s.part) == (day, part));
+
+        // The site makes the whole account wait, not only the day and part that were sent.
+        if let Some(until) = self.submissions.iter().map(|s| s.until).max() {
+            if until > now {
+                return Some(format!(
+                    "the site asked to wait, try again in {:?}",
+                    Duration::from_secs(until - now)
+                ));
+            }
+        }
+
+        // Answers that could not be checked may be sent again.
+        let checked = |s: &&Submission| !matches!(s.verdict.as_str(), "too-soon" | "unknown");
+
+        if previous.clone().any(|s| s.verdict == "correct") {
+            return Some(format!("day {day} part {part} is already solved"));
+        }
+
+        if let Some(s) = previous
+            .clone()
+            .filter(checked)
+            .find(|s| s.answer == answer)
+        {
+            return Some(format!("`{answer}` was already submitted ({})", s.verdict));
+        }
+
+        // The hints rule out every answer on the wrong side of the one they were about.
+        let number = answer.parse::<i128>().ok()?;
+
+        previous.find_map(|s| {
+            let other = s.answer.parse::<i128>().ok()?;
+
+            match s.verdict.as_str() {
+                "too-high" if number >= other => Some(format!("`{other}` was already too high")),
+                "too-low" if number <= other => Some(format!("`{other}` was already too low")),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn record(&mut self, submission: Submission) -> Result<(), LogError> {
+        self.submissions.push(submission);
+
+        let text = self
+            .submissions
+            .iter()
+            .map(|s| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\n",
+                    s.time,
+                    s.day,
+                    s.part,
+                    escape_field(&s.answer),
+                    s.verdict,
+                    s.until
+                )
+            })
+            .collect::<String>();
+
+        let io_error = |source| LogError::Io {
+            path: self.path.clone(),
+            source,
+        };
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+
+        std::fs::write(&self.path, text).map_err(io_error)
+    }
+}
+
+fn parse_line(line: &str) -> Option<Submission> {
+    let fields = line.split('\t').collect::<Vec<_>>();
+    let &[time, day, part, answer, verdict, until] = fields.as_slice() else {
+        return None;
+    };
+
+    Some(Submission {
+        time: time.parse().ok()?,
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+        answer: unescape_field(answer)?,
+        verdict: verdict.to_string(),
+        until: until.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_line, Log};
+    use std::path::PathBuf;
+
+    fn log(lines: &[&str]) -> Log {
+        Log {
+            path: PathBuf::new(),
+            submissions: lines.iter().map(|l| parse_line(l).unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn refuses_answers_that_are_known() {
+        let log = log(&[
+            "100\t1\t1\t500\ttoo-high\t160",
+            "200\t1\t1\t300\ttoo-low\t260",
+            "300\t1\t1\t400\ttoo-soon\t360",
+            "400\t2\t1\t12\tcorrect\t0",
+        ]);
+
+        assert!(log.refusal(1, 1, "450", 200).is_some());
+        assert_eq!(log.refusal(1, 1, "450", 400), None);
+        assert_eq!(log.refusal(1, 1, "400", 400), None);
+        assert!(log.refusal(1, 1, "500", 400).is_some());
+        assert!(log.refusal(1, 1, "600", 400).is_some());
+        assert!(log.refusal(1, 1, "250", 400).is_some());
+        assert!(log.refusal(2, 1, "13", 400).is_some());
+        assert_eq!(log.refusal(2, 2, "13", 400), None);
+    }
+
+    #[test]
+    fn answers_are_unescaped() {
+        let submission = parse_line("100\t1\t1\ta\\tb\\\\\twrong\t0").unwrap();
+        assert_eq!(submission.answer, "a\tb\\");
+        assert!(parse_line("100\t1\t1\ta\\\twrong\t0").is_none());
+    }
+
+    #[test]
+    fn waits_for_other_days() {
+        let log = log(&["100\t3\t2\t7\twrong\t160"]);
+
+        assert!(log.refusal(1, 1, "7", 150).is_some());
+        assert_eq!(log.refusal(1, 1, "7", 160), None);
+        assert!(log.refusal(3, 2, "7", 160).is_some());
+    }
+}
@@ -1,10 +1,96 @@
+use crate::error::SolveError;
+use std::io::BufRead;
+use std::ops::Range;
 use std::str::FromStr;
 
-pub fn parse_whitespace_separated_items<T: FromStr>(s: &str) -> Vec<T> {
-    s.trim()
-        .split_ascii_whitespace()
-        .map(|s| s.parse::<T>().ok().unwrap())
-        .collect()
+pub fn parse<T: FromStr>(s: &str) -> Result<T, SolveError> {
+    s.parse::<T>().map_err(|_| {
+        SolveError::new(
+            s,
+            format!("`{s}` is not a valid {}", std::any::type_name::<T>()),
+        )
+    })
+}
+
+pub fn parse_whitespace_separated_items<T: FromStr>(s: &str) -> Result<Vec<T>, SolveError> {
+    s.split_ascii_whitespace().map(parse::<T>).collect()
+}
+
+// Width and height of a rectangular grid with one row per line.
+pub fn grid_size(input: &str) -> Result<(usize, usize), SolveError> {
+    let mut lines = input.lines();
+
+    let width = match lines.next() {
+        Some(first) if !first.is_empty() => first.len(),
+        _ => {
+            return Err(SolveError::new(
+                input,
+                "expected a grid, but the input is empty",
+            ))
+        }
+    };
+
+    let mut height = 1;
+
+    for line in lines {
+        if line.len() != width {
+            return Err(SolveError::new(
+                line,
+                format!("expected a row of {width} tiles, found {}", line.len()),
+            ));
+        }
+
+        height += 1;
+    }
+
+    Ok((width, height))
+}
+
+// The lines of an input without their line endings, either from a text in memory or read one at a
+// time, so that inputs that do not fit in memory can be solved too. This is not an `Iterator`,
+// because a line that was read borrows the buffer it was read into.
+pub enum Lines<'a> {
+    Text(std::str::Lines<'a>),
+    Reader {
+        reader: &'a mut dyn BufRead,
+        buf: String,
+    },
+}
+
+impl<'a> Lines<'a> {
+    pub fn reader(reader: &'a mut dyn BufRead) -> Lines<'a> {
+        Lines::Reader {
+            reader,
+            buf: String::new(),
+        }
+    }
+
+    pub fn next_line(&mut self) -> Option<Result<&str, SolveError>> {
+        match self {
+            Lines::Text(lines) => lines.next().map(Ok),
+            Lines::Reader { reader, buf } => {
+                buf.clear();
+
+                match reader.read_line(buf) {
+                    Ok(0) => None,
+                    Ok(_) => {
+                        let line = buf.strip_suffix('\n').unwrap_or(buf);
+                        Some(Ok(line.strip_suffix('\r').unwrap_or(line)))
+                    }
+                    Err(e) => Some(Err(SolveError::new(
+                        "",
+                        format!("could not read the input: {e}"),
+                    ))),
+                }
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Lines<'a> {
+    fn from(text: &'a str) -> Lines<'a> {
+        Lines::Text(text.lines())
+    }
 }
 
 // Greatest Common Divisor
@@ -29,6 +115,7 @@ pub fn lcm(a: i64, b: i64) -> i64 {
 
 // Give `a` and `b` calculate Bezout's coefficients `x` and `y` and the greatest common divisor
 // (gcd). Bezout's coefficients are used in Bezout's identity: ax + by = gcd(a, b).
+#[allow(dead_code)]
 fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
     let (mut or, mut r) = (a, b);
     let (mut os, mut s) = (1, 0);
@@ -51,3 +138,103 @@ fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
 //     let z = (a0 * y * n1 + a1 * x * n0) % (n0 * n1);
 //     (if z < 0 { z + (n0 + n1) } else { z }, n0 * n1)
 // }).unwrap();
+
+// 64-bit FNV-1a hash of the concatenated `parts`. Unlike the hashers in std it is the same on
+// every platform and Rust version, so it can be stored.
+pub fn fnv1a<'a>(parts: impl IntoIterator<Item = &'a [u8]>) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+
+    for &b in parts.into_iter().flatten() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+// Escapes the backslashes, tabs and line breaks in `s`, so it fits in a field of a file with a line
+// per record and tabs between the fields.
+pub fn escape_field(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '\\' => escaped += "\\\\",
+            '\t' => escaped += "\\t",
+            '\n' => escaped += "\\n",
+            '\r' => escaped += "\\r",
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// The reverse of `escape_field`, `None` if `s` has an escape that `escape_field` does not make.
+pub fn unescape_field(s: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        unescaped.push(match chars.next()? {
+            '\\' => '\\',
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            _ => return None,
+        });
+    }
+
+    Some(unescaped)
+}
+
+// Small seedable pseudo random number generator (SplitMix64). The same seed always gives the same
+// numbers, which is all we need for resampling and generating inputs.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniformly distributed number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    // Uniformly distributed number in `range`, which must not be empty.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "an empty range to pick from");
+        range.start + self.below(range.start.abs_diff(range.end)) as i64
+    }
+
+    // Uniformly distributed index into something of length `len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::input::{InputError, Loader};
+use crate::registry::Solver;
+use crate::runner::{self, Outcome};
+
+pub const DEFAULT_PATH: &str = "answers.txt";
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "could not read {}: {source}", path.display())
+            }
+            AnswersError::Parse {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{line}: {message}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnswersError::Io { source, .. } => Some(source),
+            AnswersError::Parse { .. } => None,
+        }
+    }
+}
+
+// The expected answers, keyed by day, part and the id of the input they belong to. Every line of
+// the file is `<day> <part> <input id> <answer>`, empty lines and lines starting with `#` are
+// ignored. The answer is the rest of the line, so it may contain spaces.
+#[derive(Default)]
+pub struct Answers {
+    answers: HashMap<(u8, u8, String), Answer>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        let text = std::fs::read_to_string(path).map_err(|source| AnswersError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        Answers::parse(&text).map_err(|(line, message)| AnswersError::Parse {
+            path: path.to_path_buf(),
+            line,
+            message,
+        })
+    }
+
+    // On failure returns the 1-based line number and what is wrong with it.
+    fn parse(text: &str) -> Result<Answers, (usize, String)> {
+        let mut answers = Answers::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields = line.splitn(4, ' ').collect::<Vec<_>>();
+            let &[day, part, id, answer] = fields.as_slice() else {
+                return Err((i + 1, "expected `<day> <part> <input id> <answer>`".into()));
+            };
+
+            let day = match day.parse::<u8>() {
+                Ok(day @ 1..=25) => day,
+                _ => return Err((i + 1, format!("`{day}` is not a valid day"))),
+            };
+
+            let part = match part.parse::<u8>() {
+                Ok(part @ 1..=2) => part,
+                _ => return Err((i + 1, format!("`{part}` is not a valid part"))),
+            };
+
+            let Ok(answer) = answer.trim().parse::<Answer>();
+            let key = (day, part, id.to_string());
+
+            if answers.answers.insert(key, answer).is_some() {
+                return Err((
+                    i + 1,
+                    format!("day {day} part {part} for `{id}` is listed twice"),
+                ));
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, part: u8, id: &str) -> Option<&Answer> {
+        self.answers.get(&(day, part, id.to_string()))
+    }
+}
+
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    // There is no expected answer for this day and part
+    Missing,
+    Panic(String),
+    Error(String),
+    Timeout(Duration),
+    Skipped(String),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Fail { .. } | Verdict::Panic(_) | Verdict::Error(_) | Verdict::Timeout(_)
+        )
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Missing => "MISSING",
+            Verdict::Panic(_) => "PANIC",
+            Verdict::Error(_) => "ERROR",
+            Verdict::Timeout(_) => "TIMEOUT",
+            Verdict::Skipped(_) => "SKIP",
+        }
+    }
+}
+
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    pub verdict: Verdict,
+}
+
+// Runs every solver in `solvers` and compares its answer to the expected one. Each input is only
+// loaded once, so `solvers` should be ordered by day.
+pub fn verify(
+    solvers: impl Iterator<Item = &'static Solver>,
+    loader: &Loader,
+    answers: &Answers,
+    include_slow: bool,
+    limit: Option<Duration>,
+) -> Vec<Check> {
+    let id = loader.id();
+    let mut input = None;
+    let mut checks = Vec::new();
+
+    for solver in solvers {
+        let check = |answer, verdict| Check {
+            day: solver.day,
+            part: solver.part,
+            answer,
+            verdict,
+        };
+
+        if let Some(reason) = runner::skip_reason(solver, include_slow) {
+            checks.push(check(None, Verdict::Skipped(reason.to_string())));
+            continue;
+        }
+
+        if input.as_ref().is_none_or(|(day, _)| *day != solver.day) {
+            input = Some((solver.day, loader.load(solver.day).map(Arc::<str>::from)));
+        }
+
+        let text = match &input {
+            Some((_, Ok(text))) => text,
+            // Days without an input yet are left out, like when running them
+            Some((_, Err(e @ InputError::NotFound { .. }))) => {
+                checks.push(check(None, Verdict::Skipped(e.to_string())));
+                continue;
+            }
+            Some((_, Err(e))) => {
+                checks.push(check(None, Verdict::Error(e.to_string())));
+                continue;
+            }
+            None => unreachable!(),
+        };
+
+        let verdict = match runner::execute_with_limit(solver, text, limit) {
+            Outcome::Answer(answer) => {
+                let verdict = match answers.get(solver.day, solver.part, &id) {
+                    None => Verdict::Missing,
+                    Some(expected) if *expected == answer => Verdict::Pass,
+                    Some(expected) => Verdict::Fail {
+                        expected: expected.clone(),
+                    },
+                };
+
+                checks.push(check(Some(answer), verdict));
+                continue;
+            }
+            Outcome::Error(e) => Verdict::Error(e.to_string()),
+            Outcome::Panic(message) => Verdict::Panic(message),
+            Outcome::Unimplemented(_) => Verdict::Skipped("not implemented".to_string()),
+            Outcome::Timeout(limit) => Verdict::Timeout(limit),
+        };
+
+        checks.push(check(None, verdict));
+    }
+
+    checks
+}
+
+pub fn print_table(checks: &[Check]) {
+    println!("Day | Part | Result  | Answer");
+
+    for c in checks {
+        print!("{:3} | {:4} | {:7} | ", c.day, c.part, c.verdict.label());
+
+        match (&c.answer, &c.verdict) {
+            (Some(answer), Verdict::Fail { expected }) => {
+                println!("{answer} (expected {expected})")
+            }
+            (Some(answer), _) => println!("{answer}"),
+            (None, Verdict::Panic(message) | Verdict::Error(message)) => {
+                // Only the first line fits in the table
+                println!("{}", message.lines().next().unwrap_or(""))
+            }
+            (None, Verdict::Timeout(limit)) => println!("no answer after {limit:?}"),
+            (None, Verdict::Skipped(reason)) => println!("{reason}"),
+            (None, _) => println!(),
+        }
+    }
+
+    let count = |label| checks.iter().filter(|c| c.verdict.label() == label).count();
+
+    println!(
+        "\n{} passed, {} failed, {} missing, {} panicked, {} errors, {} timed out, {} skipped",
+        count("PASS"),
+        count("FAIL"),
+        count("MISSING"),
+        count("PANIC"),
+        count("ERROR"),
+        count("TIMEOUT"),
+        count("SKIP"),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, DEFAULT_PATH};
+    use crate::answer::Answer;
+    use crate::input::{self, Loader, Source};
+    use crate::registry::{self, Days, Status};
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    // Parts whose puzzle text has no example.
+    const NO_EXAMPLE: &[(u8, u8)] = &[(20, 2)];
+
+    #[test]
+    fn parse_answers_file() {
+        let text = "# day part input answer\n\n1 1 default 55971\n8 2 default 13830919117339\n25 1 other Merry Christmas\n";
+        let answers = Answers::parse(text).unwrap();
+
+        assert_eq!(answers.get(1, 1, "default"), Some(&Answer::from(55971u32)));
+        assert_eq!(
+            answers.get(8, 2, "default"),
+            Some(&Answer::from(13830919117339u64))
+        );
+        assert_eq!(
+            answers.get(25, 1, "other"),
+            Some(&Answer::from("Merry Christmas"))
+        );
+        assert_eq!(answers.get(1, 2, "default"), None);
+        assert_eq!(answers.get(25, 1, "default"), None);
+    }
+
+    #[test]
+    fn parse_answers_file_rejects_bad_lines() {
+        assert_eq!(Answers::parse("1 1 default").err().map(|e| e.0), Some(1));
+        assert_eq!(
+            Answers::parse("\n26 1 default 3").err().map(|e| e.0),
+            Some(2)
+        );
+        assert_eq!(Answers::parse("1 3 default 3").err().map(|e| e.0), Some(1));
+        assert_eq!(
+            Answers::parse("1 1 default 3\n1 1 default 4")
+                .err()
+                .map(|e| e.0),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn solvers_pass_their_examples() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let answers = Answers::load(&root.join(DEFAULT_PATH)).unwrap();
+        let dir = root.join(input::DEFAULT_DIR);
+        let mut checked = HashSet::new();
+
+        for entry in std::fs::read_dir(dir.join(input::EXAMPLES_DIR)).unwrap() {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            let Some((day, example)) = name
+                .strip_prefix("day_")
+                .and_then(|s| s.strip_suffix(".txt"))
+                .and_then(|s| s.split_once('_'))
+            else {
+                continue;
+            };
+
+            let (day, example) = (day.parse().unwrap(), example.parse().unwrap());
+            let loader = Loader::new(dir.clone(), Source::Example(example));
+            let text = loader.load(day).unwrap();
+
+            for solver in registry::select(Days::single(day), None) {
+                let key = (day, solver.part);
+                if solver.status == Status::Unimplemented {
+                    continue;
+                }
+
+                let Some(expected) = answers.get(day, solver.part, &loader.id()) else {
+                    continue;
+                };
+
+                assert_eq!(
+                    (solver.run)(&text).as_ref(),
+                    Ok(expected),
+                    "{} on example {example}",
+                    solver.name
+                );
+                checked.insert(key);
+            }
+        }
+
+        for solver in registry::SOLVERS {
+            let key = (solver.day, solver.part);
+            if solver.status == Status::Unimplemented || NO_EXAMPLE.contains(&key) {
+                continue;
+            }
+
+            assert!(checked.contains(&key), "{} has no example", solver.name);
+        }
+    }
+}
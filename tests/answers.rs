@@ -0,0 +1,49 @@
+use std::path::Path;
+use std::time::Duration;
+
+use aoc2023::input::{self, Loader, Source};
+use aoc2023::registry::{self, Days};
+use aoc2023::verify::{self, Answers};
+
+// Every part that is not slow gives the expected answer for the puzzle inputs. Days without an
+// expected answer, such as the ones whose input is not checked in, are left out.
+#[test]
+fn solvers_give_the_expected_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let answers = Answers::load(&root.join(verify::DEFAULT_PATH)).unwrap();
+    let loader = Loader::new(root.join(input::DEFAULT_DIR), Source::Default);
+    let id = loader.id();
+
+    let solvers =
+        registry::select(Days::ALL, None).filter(|s| answers.get(s.day, s.part, &id).is_some());
+    let checks = verify::verify(
+        solvers,
+        &loader,
+        &answers,
+        false,
+        Some(Duration::from_secs(60)),
+    );
+
+    assert!(!checks.is_empty());
+
+    for check in &checks {
+        assert!(
+            !check.verdict.is_failure(),
+            "day {} part {}: {} ({:?})",
+            check.day,
+            check.part,
+            check.verdict.label(),
+            check.answer.as_ref().map(|a| a.to_string()),
+        );
+    }
+}
+
+// The days can be called directly, without going through the registry.
+#[test]
+fn days_are_callable() {
+    let example = include_str!("../input/examples/day_25_1.txt");
+    let wiring = aoc2023::day25::parse(example).unwrap();
+    assert_eq!(aoc2023::day25::part1(&wiring), Ok(54));
+
+    assert_eq!(aoc2023::util::lcm(4, 6), 12);
+}